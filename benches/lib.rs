@@ -1 +1,100 @@
+use btree_reducer::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
+/// A root OR gate above `n` leaves.
+fn wide(n: usize) -> BTreeReducer<bool> {
+    let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+    for _ in 0..n {
+        reducer
+            .add_gate_with(GateBuilder::new(reducer.root()))
+            .unwrap();
+    }
+    let _: bool = reducer.output();
+    reducer
+}
+
+/// A chain of `n` alternating AND and OR gates, each with one leaf.
+fn deep(n: usize) -> BTreeReducer<bool> {
+    let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+    let mut parent: GateId = reducer.root();
+    for i in 0..n {
+        reducer.add_gate_with(GateBuilder::new(parent)).unwrap();
+        parent = reducer
+            .add_gate_with(GateBuilder::new(parent).program(i % 2 == 0))
+            .unwrap();
+    }
+    let _: bool = reducer.output();
+    reducer
+}
+
+/// Alternately inverts and restores the first input of the reducer and
+/// reads its output. `reinput` re-evaluates only the ancestors of the
+/// inverted leaf, after which `output` reads the stored output of the
+/// root, whereas `resolve` invalidates the reducer first so that
+/// `output` resolves every gate as it used to. `evaluate` walks the
+/// whole branch of the root without transitioning the reducer.
+fn invert_input(c: &mut Criterion, name: &str, circuit: fn(usize) -> BTreeReducer<bool>) {
+    let mut group = c.benchmark_group(name);
+    for n in [16, 64, 256].iter() {
+        let mut reducer: BTreeReducer<bool> = circuit(*n);
+        let iv: Vec<bool> = reducer.input();
+        let mut inverted: Vec<bool> = iv.clone();
+        inverted[0] = !inverted[0];
+        let ivs: [Vec<bool>; 2] = [iv, inverted];
+        let mut i: usize = 0;
+        group.bench_with_input(BenchmarkId::new("reinput", n), n, |b, _| {
+            b.iter(|| -> bool {
+                i ^= 1;
+                reducer.reinput(ivs[i].clone()).unwrap();
+                reducer.output()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("resolve", n), n, |b, _| {
+            b.iter(|| -> bool {
+                i ^= 1;
+                reducer.invalidate();
+                reducer.reinput(ivs[i].clone()).unwrap();
+                reducer.output()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("evaluate", n), n, |b, _| {
+            b.iter(|| -> bool {
+                i ^= 1;
+                reducer.evaluate(&ivs[i]).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Evaluates every input vector of a deep circuit, one at a time and
+/// 64 at a time.
+fn many_inputs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("many");
+    let reducer: BTreeReducer<bool> = deep(16);
+    let ivs: Vec<Vec<bool>> = (0..1024)
+        .map(|i: usize| -> Vec<bool> { (0..17).map(|j| -> bool { (i >> j) & 1 == 1 }).collect() })
+        .collect();
+    group.bench_function("evaluate", |b| {
+        b.iter(|| -> Vec<bool> {
+            ivs.iter()
+                .map(|iv| -> bool { reducer.evaluate(iv).unwrap() })
+                .collect()
+        })
+    });
+    group.bench_function("evaluate_many", |b| {
+        b.iter(|| -> Vec<bool> { reducer.evaluate_many(&ivs).unwrap() })
+    });
+    group.finish();
+}
+
+fn wide_circuit(c: &mut Criterion) {
+    invert_input(c, "wide", wide);
+}
+
+fn deep_circuit(c: &mut Criterion) {
+    invert_input(c, "deep", deep);
+}
+
+criterion_group!(benches, wide_circuit, deep_circuit, many_inputs);
+criterion_main!(benches);
@@ -0,0 +1,648 @@
+//! The AIGER format of And-Inverter Graphs for `BTreeReducer<bool>`,
+//! in both its ASCII (`aag`) and binary (`aig`) variants.
+//!
+//! ```text
+//! aag 5 2 0 1 3
+//! 2
+//! 4
+//! 10
+//! 6 5 3
+//! 8 4 2
+//! 10 9 7
+//! i0 a
+//! i1 b
+//! o0 and
+//! ```
+//!
+//! Variable `v` has the even literal `2v` and the inverted literal
+//! `2v + 1`, variable zero (0) being the constant zero (0). Every
+//! input gate is an input variable, ordered as for `Reinput<Vec<bool>>`,
+//! and every output gate, beginning with the root, is an output. A
+//! series (AND) gate is a chain of AND nodes, a parallel (OR) gate is
+//! the inverted AND of its inverted children, and a configured gate
+//! inverts its literal. Only combinational graphs, i.e. those without
+//! latches, are supported.
+
+use crate::graph::postorder;
+use crate::parse::{
+    connect, duplicate, invalid, tokenize, unexpected, ParseError, ParseErrorKind, Scaffold, Token,
+};
+use crate::reducer::api::{AddOutput, Configuration, Program, Reconfigure, Reprogram};
+use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+mod test;
+
+/// `Aig` is an And-Inverter Graph whose AND nodes are numbered after
+/// its inputs in the order in which they are defined.
+struct Aig {
+    inputs: usize,
+    outputs: Vec<usize>,
+    // The inputs of every AND node, the greater first.
+    ands: Vec<(usize, usize)>,
+    symbols: String,
+}
+
+/// Parses an ASCII or binary AIGER graph into a `BTreeReducer<bool>`,
+/// depending on whether it begins with `aag` or `aig`. Every input is a
+/// single input gate, labeled by its symbol if it has one, and every
+/// AND node a gate. Chains of AND nodes which are not shared are merged
+/// into a single series (AND) gate, or a parallel (OR) gate if the chain
+/// and every one of its inputs are inverted. The root computes the first output
+/// and every other output is an output gate of its own, labeled by its
+/// symbol unless the gate is already labeled; an output whose literal
+/// is already an output passes it through a gate of its own.
+///
+/// Any input or output which is not otherwise connected to the root
+/// hangs beneath a gate whose output is the identity of the root, as
+/// for `blif::parse`. A graph must have at least one (1) input and
+/// output. A binary graph need not be valid UTF-8, so both variants are
+/// read from bytes; errors within the AND nodes of a binary graph are
+/// located by the byte following the start of their line.
+pub fn parse(data: &[u8]) -> Result<BTreeReducer<bool>, ParseError> {
+    let mut cursor: Cursor<'_> = Cursor {
+        data,
+        position: 0,
+        line: 0,
+        start: 0,
+    };
+    let (line_number, line): (usize, &str) = match cursor.read_line()? {
+        Some(l) => l,
+        None => return Err(ParseError::new(1, 1, ParseErrorKind::UnexpectedEnd)),
+    };
+    let header: Vec<Token<'_>> = tokenize(line, '\n');
+    let binary: bool = match header.first() {
+        Some(t) if t.text == "aag" => false,
+        Some(t) if t.text == "aig" => true,
+        Some(t) => return Err(unexpected(line_number, t.column, t.text)),
+        None => {
+            return Err(ParseError::new(
+                line_number,
+                1,
+                ParseErrorKind::UnexpectedEnd,
+            ))
+        }
+    };
+    let [m, i, l, o, a]: [(Token<'_>, usize); 5] = numbers(line_number, line, &header[1..], 5)?
+        .try_into()
+        .unwrap();
+    if l.1 != 0 {
+        return Err(invalid(line_number, l.0.column, l.0.text));
+    }
+    if m.1 < i.1 + a.1 || (binary && m.1 != i.1 + a.1) {
+        return Err(invalid(line_number, m.0.column, m.0.text));
+    }
+    let max: usize = 2 * m.1 + 1;
+
+    // The definition of every variable, located by its line and column.
+    let mut defined: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+    let mut inputs: Vec<usize> = Vec::new();
+    for k in 0..i.1 {
+        if binary {
+            defined.insert(k + 1, (line_number, 1));
+            inputs.push(k + 1);
+            continue;
+        }
+        let (line_number, line): (usize, &str) = cursor.expect_line()?;
+        let tokens: Vec<Token<'_>> = tokenize(line, '\n');
+        let [(t, literal)]: [(Token<'_>, usize); 1] =
+            numbers(line_number, line, &tokens, 1)?.try_into().unwrap();
+        if literal < 2 || literal % 2 == 1 || literal > max {
+            return Err(invalid(line_number, t.column, t.text));
+        }
+        if defined
+            .insert(literal / 2, (line_number, t.column))
+            .is_some()
+        {
+            return Err(duplicate(line_number, t.column, t.text));
+        }
+        inputs.push(literal / 2);
+    }
+    let mut outputs: Vec<(usize, usize, usize)> = Vec::new();
+    for _ in 0..o.1 {
+        let (line_number, line): (usize, &str) = cursor.expect_line()?;
+        let tokens: Vec<Token<'_>> = tokenize(line, '\n');
+        let [(t, literal)]: [(Token<'_>, usize); 1] =
+            numbers(line_number, line, &tokens, 1)?.try_into().unwrap();
+        if literal > max {
+            return Err(invalid(line_number, t.column, t.text));
+        }
+        outputs.push((literal, line_number, t.column));
+    }
+    let mut ands: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+    for k in 0..a.1 {
+        let (lhs, rhs0, rhs1): (usize, usize, usize) = if binary {
+            let lhs: usize = 2 * (i.1 + k + 1);
+            let (line, column): (usize, usize) = (cursor.line + 1, cursor.column());
+            let delta0: usize = cursor.read_delta()?;
+            let delta1: usize = cursor.read_delta()?;
+            if delta0 == 0 || delta0 > lhs || delta1 > lhs - delta0 {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    ParseErrorKind::InvalidValue(format!("{} {}", delta0, delta1)),
+                ));
+            }
+            defined.insert(lhs / 2, (line, column));
+            (lhs, lhs - delta0, lhs - delta0 - delta1)
+        } else {
+            let (line_number, line): (usize, &str) = cursor.expect_line()?;
+            let tokens: Vec<Token<'_>> = tokenize(line, '\n');
+            let [(t, lhs), (t0, rhs0), (t1, rhs1)]: [(Token<'_>, usize); 3] =
+                numbers(line_number, line, &tokens, 3)?.try_into().unwrap();
+            if lhs < 2 || lhs % 2 == 1 || lhs > max {
+                return Err(invalid(line_number, t.column, t.text));
+            }
+            for (t, rhs) in [(t0, rhs0), (t1, rhs1)].iter() {
+                if *rhs > max {
+                    return Err(invalid(line_number, t.column, t.text));
+                }
+            }
+            if defined.insert(lhs / 2, (line_number, t.column)).is_some() {
+                return Err(duplicate(line_number, t.column, t.text));
+            }
+            (lhs, rhs0, rhs1)
+        };
+        ands.insert(lhs / 2, (rhs0, rhs1));
+    }
+    let (input_symbols, output_symbols) = symbols(&mut cursor, i.1, o.1)?;
+    if inputs.is_empty() || outputs.is_empty() {
+        return Err(ParseError::new(
+            cursor.line + 1,
+            1,
+            ParseErrorKind::UnexpectedEnd,
+        ));
+    }
+    check(&defined, &outputs, &ands)?;
+
+    let mut fanout: BTreeMap<usize, usize> = BTreeMap::new();
+    for (rhs0, rhs1) in ands.values() {
+        *fanout.entry(rhs0 / 2).or_insert(0) += 1;
+        *fanout.entry(rhs1 / 2).or_insert(0) += 1;
+    }
+    for (literal, _, _) in outputs.iter() {
+        *fanout.entry(literal / 2).or_insert(0) += 1;
+    }
+    let (scaffold, ids): (Scaffold, Vec<GateId>) = Scaffold::new(
+        (0..inputs.len()).map(|k| -> Option<&str> { input_symbols.get(&k).cloned() }),
+    );
+    let mut builder: Builder = Builder {
+        scaffold,
+        ands,
+        fanout,
+        gates: BTreeMap::new(),
+        constant: 2 * inputs[0],
+    };
+    for (input, id) in inputs.iter().zip(ids) {
+        builder.gates.insert(2 * input, id);
+    }
+    let root: GateId = builder.scaffold.reducer.root();
+    // The root may only compute the first output itself if nothing
+    // else refers to its variable, otherwise it passes it through.
+    let first: usize = outputs[0].0;
+    let mut gate: Gate<bool> = builder.scaffold.reducer.gate(root).unwrap().clone();
+    if let Some(symbol) = output_symbols.get(&0) {
+        gate.relabel(symbol);
+    }
+    if inputs.contains(&(first / 2)) {
+        gate.reprogram(true).unwrap();
+        gate.reconfigure(first % 2 == 1).unwrap();
+        builder.scaffold.reducer.update(root, gate).unwrap();
+        builder.attach(first & !1, root);
+    } else if first / 2 == 0 || builder.fanout[&(first / 2)] == 1 {
+        builder.scaffold.reducer.update(root, gate).unwrap();
+        builder.define(first, root);
+    } else {
+        gate.reprogram(true).unwrap();
+        builder.scaffold.reducer.update(root, gate).unwrap();
+        builder.attach(first, root);
+    }
+    for (k, (literal, _, _)) in outputs.iter().enumerate().skip(1) {
+        let sink: GateId = builder.scaffold.sink();
+        let id: GateId = match builder.gates.get(literal).cloned() {
+            // A literal which is already an output is passed through a
+            // gate of its own, so that every output is a distinct gate.
+            Some(id) if builder.scaffold.reducer.outputs().contains(&id) => {
+                let buffer: GateId = builder
+                    .scaffold
+                    .reducer
+                    .add_gate_with(GateBuilder::new(sink))
+                    .unwrap();
+                connect(&mut builder.scaffold.reducer, buffer, id);
+                buffer
+            }
+            Some(id) => id,
+            None => builder.attach(*literal, sink),
+        };
+        builder.scaffold.reducer.add_output(id).unwrap();
+        if let Some(symbol) = output_symbols.get(&k) {
+            if builder.scaffold.reducer.gate(id).unwrap().label().is_none() {
+                let mut gate: Gate<bool> = builder.scaffold.reducer.gate(id).unwrap().clone();
+                gate.relabel(symbol);
+                builder.scaffold.reducer.update(id, gate).unwrap();
+            }
+        }
+    }
+    if let Some(sink) = builder.scaffold.prune() {
+        builder.attach(builder.constant, sink);
+        builder.attach(builder.constant + 1, sink);
+    }
+    Ok(builder.scaffold.finish())
+}
+
+/// Prints a `BTreeReducer<bool>` as an ASCII AIGER graph which `parse`
+/// accepts. Labeled input and output gates are named in the symbol
+/// table.
+pub fn print(reducer: &BTreeReducer<bool>) -> String {
+    let aig: Aig = aig(reducer);
+    let mut s: String = format!(
+        "aag {} {} 0 {} {}\n",
+        aig.inputs + aig.ands.len(),
+        aig.inputs,
+        aig.outputs.len(),
+        aig.ands.len()
+    );
+    for k in 0..aig.inputs {
+        s.push_str(&format!("{}\n", 2 * (k + 1)));
+    }
+    for literal in aig.outputs.iter() {
+        s.push_str(&format!("{}\n", literal));
+    }
+    for (k, (rhs0, rhs1)) in aig.ands.iter().enumerate() {
+        s.push_str(&format!("{} {} {}\n", 2 * (aig.inputs + k + 1), rhs0, rhs1));
+    }
+    s.push_str(&aig.symbols);
+    s
+}
+
+/// Prints a `BTreeReducer<bool>` as a binary AIGER graph which `parse`
+/// accepts, i.e. the ASCII graph without its inputs and with every AND
+/// node encoded as the variable length differences between its literal
+/// and those of its inputs.
+pub fn print_binary(reducer: &BTreeReducer<bool>) -> Vec<u8> {
+    let aig: Aig = aig(reducer);
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(
+        format!(
+            "aig {} {} 0 {} {}\n",
+            aig.inputs + aig.ands.len(),
+            aig.inputs,
+            aig.outputs.len(),
+            aig.ands.len()
+        )
+        .as_bytes(),
+    );
+    for literal in aig.outputs.iter() {
+        data.extend_from_slice(format!("{}\n", literal).as_bytes());
+    }
+    for (k, (rhs0, rhs1)) in aig.ands.iter().enumerate() {
+        let lhs: usize = 2 * (aig.inputs + k + 1);
+        for mut delta in [lhs - rhs0, rhs0 - rhs1] {
+            // Seven (7) bits to a byte, least significant first, the
+            // high bit set on every byte but the last.
+            while delta >= 0x80 {
+                data.push((delta & 0x7f) as u8 | 0x80);
+                delta >>= 7;
+            }
+            data.push(delta as u8);
+        }
+    }
+    data.extend_from_slice(aig.symbols.as_bytes());
+    data
+}
+
+/// Converts the gates on which any output depends into AND nodes,
+/// sharing structurally identical nodes.
+fn aig(reducer: &BTreeReducer<bool>) -> Aig {
+    let inputs: Vec<GateId> = reducer.input_gates();
+    let outputs: Vec<GateId> = reducer.outputs();
+    let mut literals: BTreeMap<GateId, usize> = inputs
+        .iter()
+        .enumerate()
+        .map(|(k, id)| -> (GateId, usize) { (*id, 2 * (k + 1)) })
+        .collect();
+    let mut ands: Vec<(usize, usize)> = Vec::new();
+    let mut nodes: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for id in postorder(reducer, &outputs).iter() {
+        let gate: &Gate<bool> = reducer.gate(*id).unwrap();
+        let literal: usize = if inputs.contains(id) {
+            literals[id]
+        } else {
+            // x | y = !(!x & !y)
+            let invert: usize = !gate.program() as usize;
+            let mut children = reducer
+                .children(*id)
+                .into_iter()
+                .flatten()
+                .map(|child| -> usize { literals[child] ^ invert });
+            let first: usize = children.next().unwrap();
+            children.fold(first, |x: usize, y: usize| -> usize {
+                let node: (usize, usize) = (x.max(y), x.min(y));
+                *nodes.entry(node).or_insert_with(|| -> usize {
+                    ands.push(node);
+                    2 * (inputs.len() + ands.len())
+                })
+            }) ^ invert
+        };
+        literals.insert(*id, literal ^ gate.configuration() as usize);
+    }
+    let mut symbols: String = String::new();
+    for (prefix, ids) in [("i", &inputs), ("o", &outputs)] {
+        for (k, id) in ids.iter().enumerate() {
+            if let Some(label) = reducer.gate(*id).unwrap().label() {
+                if !label.contains('\n') {
+                    symbols.push_str(&format!("{}{} {}\n", prefix, k, label));
+                }
+            }
+        }
+    }
+    Aig {
+        inputs: inputs.len(),
+        outputs: outputs.iter().map(|id| -> usize { literals[id] }).collect(),
+        ands,
+        symbols,
+    }
+}
+
+/// `Symbols` names the inputs or the outputs of a graph by index.
+type Symbols<'a> = BTreeMap<usize, &'a str>;
+
+/// `Cursor` reads an AIGER graph line by line, or byte by byte within
+/// the AND nodes of a binary graph.
+struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+    // The number of lines read and the position at which the current
+    // line starts.
+    line: usize,
+    start: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn column(&self) -> usize {
+        self.position - self.start + 1
+    }
+
+    /// Reads the next line, along with its one (1) indexed number.
+    fn read_line(&mut self) -> Result<Option<(usize, &'a str)>, ParseError> {
+        if self.position >= self.data.len() {
+            return Ok(None);
+        }
+        let rest: &'a [u8] = &self.data[self.position..];
+        let end: usize = rest
+            .iter()
+            .position(|b| -> bool { *b == b'\n' })
+            .unwrap_or(rest.len());
+        let line: &'a str = core::str::from_utf8(&rest[..end]).map_err(|e| -> ParseError {
+            ParseError::new(
+                self.line + 1,
+                e.valid_up_to() + 1,
+                ParseErrorKind::InvalidValue(String::from_utf8_lossy(&rest[..end]).into()),
+            )
+        })?;
+        self.line += 1;
+        self.position += end + 1;
+        self.start = self.position;
+        Ok(Some((self.line, line)))
+    }
+
+    fn expect_line(&mut self) -> Result<(usize, &'a str), ParseError> {
+        self.read_line()?.ok_or_else(|| -> ParseError {
+            ParseError::new(self.line + 1, 1, ParseErrorKind::UnexpectedEnd)
+        })
+    }
+
+    /// Reads a variable length unsigned integer of a binary graph.
+    fn read_delta(&mut self) -> Result<usize, ParseError> {
+        let mut delta: usize = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let byte: u8 = match self.data.get(self.position) {
+                Some(b) => *b,
+                None => {
+                    return Err(ParseError::new(
+                        self.line + 1,
+                        self.column(),
+                        ParseErrorKind::UnexpectedEnd,
+                    ))
+                }
+            };
+            if shift > usize::BITS - 7 {
+                return Err(ParseError::new(
+                    self.line + 1,
+                    self.column(),
+                    ParseErrorKind::InvalidValue(format!("{:#04x}", byte)),
+                ));
+            }
+            delta |= ((byte & 0x7f) as usize) << shift;
+            self.position += 1;
+            if byte & 0x80 == 0 {
+                return Ok(delta);
+            }
+            shift += 7;
+        }
+    }
+}
+
+/// Parses exactly `count` numbers from `tokens`.
+fn numbers<'a>(
+    line_number: usize,
+    line: &str,
+    tokens: &[Token<'a>],
+    count: usize,
+) -> Result<Vec<(Token<'a>, usize)>, ParseError> {
+    if let Some(t) = tokens.get(count) {
+        return Err(unexpected(line_number, t.column, t.text));
+    }
+    if tokens.len() < count {
+        return Err(ParseError::new(
+            line_number,
+            line.chars().count() + 1,
+            ParseErrorKind::UnexpectedEnd,
+        ));
+    }
+    tokens
+        .iter()
+        .map(|t| -> Result<(Token<'a>, usize), ParseError> {
+            match t.text.parse::<usize>() {
+                Ok(n) if t.text.bytes().all(|b| -> bool { b.is_ascii_digit() }) => Ok((*t, n)),
+                _ => Err(invalid(line_number, t.column, t.text)),
+            }
+        })
+        .collect()
+}
+
+/// Reads the symbol table, up to any comment section, returning the
+/// names of the inputs and of the outputs by index.
+fn symbols<'a>(
+    cursor: &mut Cursor<'a>,
+    inputs: usize,
+    outputs: usize,
+) -> Result<(Symbols<'a>, Symbols<'a>), ParseError> {
+    let mut input_symbols: Symbols<'a> = BTreeMap::new();
+    let mut output_symbols: Symbols<'a> = BTreeMap::new();
+    while let Some((line_number, line)) = cursor.read_line()? {
+        if line == "c" {
+            break;
+        }
+        let (position, name): (&str, &str) = line.split_once(' ').unwrap_or((line, ""));
+        let t: Token<'_> = Token {
+            column: 1,
+            text: position,
+        };
+        let (symbols, count): (&mut Symbols<'a>, usize) = match position.get(..1) {
+            Some("i") => (&mut input_symbols, inputs),
+            Some("o") => (&mut output_symbols, outputs),
+            _ => return Err(unexpected(line_number, t.column, t.text)),
+        };
+        let k: usize = match position[1..].parse::<usize>() {
+            Ok(k) if k < count && !name.is_empty() => k,
+            _ => return Err(invalid(line_number, t.column, t.text)),
+        };
+        if symbols.insert(k, name).is_some() {
+            return Err(duplicate(line_number, t.column, t.text));
+        }
+    }
+    Ok((input_symbols, output_symbols))
+}
+
+/// Ensures that every variable on which an output depends is defined
+/// and that no AND node depends on itself.
+fn check(
+    defined: &BTreeMap<usize, (usize, usize)>,
+    outputs: &[(usize, usize, usize)],
+    ands: &BTreeMap<usize, (usize, usize)>,
+) -> Result<(), ParseError> {
+    let unknown = |literal: usize, line: usize, column: usize| -> Result<(), ParseError> {
+        if literal / 2 == 0 || defined.contains_key(&(literal / 2)) {
+            Ok(())
+        } else {
+            Err(ParseError::new(
+                line,
+                column,
+                ParseErrorKind::UnknownName(format!("{}", literal)),
+            ))
+        }
+    };
+    let mut finished: BTreeSet<usize> = BTreeSet::new();
+    let mut started: BTreeSet<usize> = BTreeSet::new();
+    for (literal, line, column) in outputs.iter() {
+        unknown(*literal, *line, *column)?;
+        let mut stack: Vec<(usize, bool)> = Vec::from([(literal / 2, false)]);
+        while let Some((v, expanded)) = stack.pop() {
+            let (rhs0, rhs1): (usize, usize) = match ands.get(&v) {
+                Some(node) => *node,
+                None => continue,
+            };
+            if expanded {
+                finished.insert(v);
+                continue;
+            }
+            if finished.contains(&v) {
+                continue;
+            }
+            let (line, column): (usize, usize) = defined[&v];
+            if !started.insert(v) {
+                return Err(ParseError::new(line, column, ParseErrorKind::InvalidEdge));
+            }
+            stack.push((v, true));
+            for rhs in [rhs0, rhs1] {
+                unknown(rhs, line, column)?;
+                if !finished.contains(&(rhs / 2)) {
+                    stack.push((rhs / 2, false));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Builder {
+    scaffold: Scaffold,
+    ands: BTreeMap<usize, (usize, usize)>,
+    // The number of AND nodes and outputs which refer to every variable.
+    fanout: BTreeMap<usize, usize>,
+    // The gate of every literal built so far.
+    gates: BTreeMap<usize, GateId>,
+    // The literal of which every constant is built, i.e. x & !x.
+    constant: usize,
+}
+
+impl Builder {
+    /// Adds the gate which computes `literal` beneath `parent`, unless
+    /// it has been built already, returning that gate.
+    fn attach(&mut self, literal: usize, parent: GateId) -> GateId {
+        if let Some(id) = self.gates.get(&literal).cloned() {
+            connect(&mut self.scaffold.reducer, parent, id);
+            return id;
+        }
+        let id: GateId = if self.gates.contains_key(&(literal & !1)) && literal % 2 == 1 {
+            // The complement of an input, or of any other gate which
+            // has been built already.
+            let id: GateId = self
+                .scaffold
+                .reducer
+                .add_gate_with(GateBuilder::new(parent).program(true).configuration(true))
+                .unwrap();
+            self.attach(literal & !1, id);
+            id
+        } else {
+            let id: GateId = self
+                .scaffold
+                .reducer
+                .add_gate_with(GateBuilder::new(parent))
+                .unwrap();
+            self.define(literal, id);
+            id
+        };
+        self.gates.insert(literal, id);
+        id
+    }
+
+    /// Shapes `id` as the gate which computes `literal`, of an AND node
+    /// or a constant, and adds its children.
+    fn define(&mut self, literal: usize, id: GateId) {
+        let mut gate: Gate<bool> = self.scaffold.reducer.gate(id).unwrap().clone();
+        let inverted: bool = literal % 2 == 1;
+        if literal / 2 == 0 {
+            gate.reprogram(true).unwrap();
+            gate.reconfigure(inverted).unwrap();
+            self.scaffold.reducer.update(id, gate).unwrap();
+            self.attach(self.constant, id);
+            self.attach(self.constant + 1, id);
+            return;
+        }
+        // The inputs of a chain of AND nodes, each of which is only
+        // referred to by the next.
+        let mut conjuncts: Vec<usize> = Vec::new();
+        let mut stack: Vec<usize> = Vec::from([literal & !1]);
+        while let Some(l) = stack.pop() {
+            match self.ands.get(&(l / 2)) {
+                Some((rhs0, rhs1))
+                    if l == literal & !1 || (l % 2 == 0 && self.fanout[&(l / 2)] == 1) =>
+                {
+                    stack.push(*rhs1);
+                    stack.push(*rhs0);
+                }
+                _ => conjuncts.push(l),
+            }
+        }
+        // The children of a gate are visited, and so numbered, in order.
+        conjuncts.sort_unstable();
+        // !(!x & !y) = x | y
+        let parallel: bool = inverted
+            && conjuncts
+                .iter()
+                .all(|l| -> bool { l % 2 == 1 && l / 2 != 0 });
+        gate.reprogram(!parallel).unwrap();
+        gate.reconfigure(inverted != parallel).unwrap();
+        self.scaffold.reducer.update(id, gate).unwrap();
+        for l in conjuncts.iter() {
+            self.attach(l ^ parallel as usize, id);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::aiger::{parse, print, print_binary};
+    use crate::equivalence::equivalent;
+    use crate::expression;
+    use crate::netlist;
+    use crate::netlist::test::XOR;
+    use crate::parse::{ParseError, ParseErrorKind};
+    use crate::reducer::api::{Output, Reinput};
+    use crate::reducer::BTreeReducer;
+    use crate::truth_table::TruthTable;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// Every output for every input vector, transitioning a clone of
+    /// `reducer`.
+    fn outputs(reducer: &BTreeReducer<bool>) -> Vec<Vec<bool>> {
+        let inputs: usize = reducer.input_gates().len();
+        (0..1 << inputs)
+            .map(|row| -> Vec<bool> {
+                let mut reducer: BTreeReducer<bool> = reducer.clone();
+                reducer
+                    .reinput(
+                        TruthTable::from_fn(inputs, |_| -> bool { false })
+                            .unwrap()
+                            .input(row),
+                    )
+                    .unwrap();
+                reducer.output()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn print_xor() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = netlist::parse(XOR)?;
+        assert_eq!(
+            print(&reducer),
+            concat!(
+                "aag 5 2 0 1 3\n",
+                "2\n",
+                "4\n",
+                "10\n",
+                "6 5 3\n",
+                "8 4 2\n",
+                "10 9 7\n",
+                "i0 a\n",
+                "i1 b\n",
+                "o0 and\n",
+            )
+        );
+        let mut binary: Vec<u8> = Vec::from(&b"aig 5 2 0 1 3\n10\n"[..]);
+        binary.extend_from_slice(&[1, 2, 4, 2, 1, 2]);
+        binary.extend_from_slice(b"i0 a\ni1 b\no0 and\n");
+        assert_eq!(print_binary(&reducer), binary);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_graph() -> Result<(), ParseError> {
+        // The gates of the reducer are recovered, though only its input
+        // and output gates are named.
+        let reducer: BTreeReducer<bool> = netlist::parse(XOR)?;
+        for parsed in [
+            parse(print(&reducer).as_bytes())?,
+            parse(&print_binary(&reducer))?,
+        ]
+        .iter()
+        {
+            assert_eq!(
+                netlist::print(parsed),
+                concat!(
+                    "root and program=1\n",
+                    "gate g4 and\n",
+                    "gate g5 and program=1 configuration=1\n",
+                    "gate a g4\n",
+                    "gate b g4\n",
+                    "short g5 a\n",
+                    "short g5 b\n",
+                )
+            );
+        }
+
+        // The sum and NAND of two inputs, defined out of order, with a
+        // constant output, an unused input and a comment.
+        let reducer: BTreeReducer<bool> = parse(
+            b"aag 6 3 0 4 3
+2
+4
+6
+12
+9
+1
+2
+12 11 9
+8 4 2
+10 3 5
+i0 x
+i1 y
+o0 sum
+o1 nand
+c
+the sum and NAND of x and y
+",
+        )?;
+        let names: Vec<Option<&str>> = reducer
+            .input_gates()
+            .into_iter()
+            .map(|id| -> Option<&str> { reducer.gate(id).unwrap().label() })
+            .collect();
+        assert_eq!(names, Vec::from([Some("x"), Some("y"), None]));
+        let expected: Vec<Vec<bool>> = (0..8)
+            .map(|row: usize| -> Vec<bool> {
+                let (x, y): (bool, bool) = (row & 1 == 1, row & 2 == 2);
+                Vec::from([x != y, !(x && y), true, x])
+            })
+            .collect();
+        assert_eq!(outputs(&reducer), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), ParseError> {
+        let mut reducers: Vec<BTreeReducer<bool>> = Vec::from([
+            netlist::parse(XOR)?,
+            netlist::parse(&(String::from(XOR) + "output nand\noutput a\noutput or"))?,
+        ]);
+        for s in [
+            "a",
+            "!a",
+            "a & !b",
+            "!(a | b) & (c | !d)",
+            "a ^ b ^ c",
+            "let x = a & b; x | !x & c",
+            "a & b & c & d & (e | f | g) & !(a & c & e)",
+        ]
+        .iter()
+        {
+            reducers.push(expression::parse(s)?);
+        }
+        for reducer in reducers.iter() {
+            let printed: String = print(reducer);
+            for parsed in [parse(printed.as_bytes())?, parse(&print_binary(reducer))?].iter() {
+                assert_eq!(outputs(parsed), outputs(reducer), "{}", printed);
+                // The graph is the same up to the numbering of its nodes.
+                assert_eq!(print(parsed).lines().next(), printed.lines().next());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_output() -> Result<(), ParseError> {
+        // Every output is a gate of its own, even where its literal is
+        // that of another output, e.g. an inverted input gate which is
+        // both the only child of the root and an output.
+        let reducer: BTreeReducer<bool> =
+            netlist::parse("root r\ngate g1 r configuration=1\noutput g1\n")?;
+        assert_eq!(print(&reducer).lines().nth(2), Some("3"));
+        assert_eq!(print(&reducer).lines().nth(3), Some("3"));
+        assert_eq!(
+            equivalent(&reducer, &parse(print(&reducer).as_bytes())?),
+            Ok(())
+        );
+        for (graph, count) in [
+            ("aag 5 2 0 2 3\n2\n4\n10\n10\n6 5 3\n8 4 2\n10 9 7\n", 2),
+            ("aag 5 2 0 3 3\n2\n4\n10\n11\n11\n6 5 3\n8 4 2\n10 9 7\n", 3),
+            ("aag 1 1 0 2 0\n2\n3\n3\n", 2),
+            ("aag 1 1 0 3 0\n2\n2\n2\n2\n", 3),
+            ("aag 1 1 0 3 0\n2\n0\n0\n0\n", 3),
+        ]
+        .iter()
+        {
+            let parsed: BTreeReducer<bool> = parse(graph.as_bytes())?;
+            assert_eq!(parsed.outputs().len(), *count, "{}", graph);
+            let reparsed: BTreeReducer<bool> = parse(print(&parsed).as_bytes())?;
+            assert_eq!(reparsed.outputs().len(), *count, "{}", graph);
+            assert_eq!(outputs(&reparsed), outputs(&parsed), "{}", graph);
+            assert_eq!(equivalent(&parsed, &reparsed), Ok(()), "{}", graph);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (s, line, column, kind) in [
+            (&b""[..], 1, 1, ParseErrorKind::UnexpectedEnd),
+            (b"aig 1 1 0 1", 1, 12, ParseErrorKind::UnexpectedEnd),
+            (
+                b"agg 1 1 0 1 0",
+                1,
+                1,
+                ParseErrorKind::UnexpectedToken(String::from("agg")),
+            ),
+            (
+                b"aag 2 1 1 1 0\n2\n4 2\n4\n",
+                1,
+                9,
+                ParseErrorKind::InvalidValue(String::from("1")),
+            ),
+            (
+                b"aag 1 1 0 1 0\n3\n2\n",
+                2,
+                1,
+                ParseErrorKind::InvalidValue(String::from("3")),
+            ),
+            (
+                b"aag 2 2 0 1 0\n2\n2\n2\n",
+                3,
+                1,
+                ParseErrorKind::DuplicateName(String::from("2")),
+            ),
+            (b"aag 1 1 0 1 0\n2\n", 3, 1, ParseErrorKind::UnexpectedEnd),
+            (
+                b"aag 2 1 0 1 1\n2\n4\n4 2 6\n",
+                4,
+                5,
+                ParseErrorKind::InvalidValue(String::from("6")),
+            ),
+            (
+                b"aag 3 1 0 1 1\n2\n4\n4 2 6\n",
+                4,
+                1,
+                ParseErrorKind::UnknownName(String::from("6")),
+            ),
+            (
+                b"aag 3 1 0 1 2\n2\n4\n4 2 6\n6 4 2\n",
+                4,
+                1,
+                ParseErrorKind::InvalidEdge,
+            ),
+            (
+                b"aig 2 1 0 1 1\n4\n\x05\x00",
+                3,
+                1,
+                ParseErrorKind::InvalidValue(String::from("5 0")),
+            ),
+            (
+                b"aig 2 1 0 1 1\n4\n\x82",
+                3,
+                2,
+                ParseErrorKind::UnexpectedEnd,
+            ),
+            (
+                b"aag 1 1 0 1 0\n2\n2\ni1 a\n",
+                4,
+                1,
+                ParseErrorKind::InvalidValue(String::from("i1")),
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                parse(s),
+                Err(ParseError::new(*line, *column, kind.clone())),
+                "{}",
+                String::from_utf8_lossy(s)
+            );
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::graph::postorder;
+use crate::reducer::api::{Configuration, Program};
+use crate::reducer::{BTreeReducer, Gate, GateId};
+use crate::Error;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+mod test;
+
+/// The index of the terminal node of the constant zero (0).
+const ZERO: usize = 0;
+/// The index of the terminal node of the constant one (1).
+const ONE: usize = 1;
+
+/// `Node` tests the input at `level` of the order, continuing with the
+/// node `low` if it is zero (0) and `high` if it is one (1). Both
+/// terminal nodes are at the level below every input.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+struct Node {
+    level: usize,
+    low: usize,
+    high: usize,
+}
+
+/// `Bdd` is the reduced ordered binary decision diagram of the output of
+/// the root of a `BTreeReducer<bool>`, i.e. of a function of its inputs,
+/// ordered as for `Reinput<Vec<bool>>`. The inputs are tested in a
+/// fixed order from the top of the diagram down; no node has two equal
+/// children and no two nodes test the same input with the same children.
+///
+/// The diagram of a function is thus unique for a given order and its
+/// size depends only on the order. Two `Bdd`s of the same order are
+/// equal exactly if their functions are.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Bdd {
+    order: Vec<usize>,
+    /// The terminal nodes followed by the decision nodes reachable from
+    /// the root, every node following its children.
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+impl Bdd {
+    /// The number of inputs.
+    pub fn inputs(&self) -> usize {
+        self.order.len()
+    }
+
+    /// The order of the inputs, from the top of the diagram down.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// The number of decision nodes, i.e. of nodes other than the
+    /// constants zero (0) and one (1).
+    pub fn nodes(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    /// Whether the output is one (1) for some input vector.
+    pub fn is_satisfiable(&self) -> bool {
+        self.root != ZERO
+    }
+
+    /// The output for the input vector `iv`, which must have an input
+    /// for every input of the diagram.
+    pub fn evaluate(&self, iv: &[bool]) -> Result<bool, Error> {
+        if iv.len() != self.inputs() {
+            return Err(Error::ImproperDimension);
+        }
+        let mut node: usize = self.root;
+        while node > ONE {
+            let Node { level, low, high } = self.nodes[node];
+            node = if iv[self.order[level]] { high } else { low };
+        }
+        Ok(node == ONE)
+    }
+
+    /// An input vector for which the output is one (1), if there is
+    /// one. Every input on which the output does not depend is zero (0).
+    pub fn satisfying_input(&self) -> Option<Vec<bool>> {
+        if !self.is_satisfiable() {
+            return None;
+        }
+        let mut iv: Vec<bool> = vec![false; self.inputs()];
+        let mut node: usize = self.root;
+        while node > ONE {
+            let Node { level, low, high } = self.nodes[node];
+            if low == ZERO {
+                iv[self.order[level]] = true;
+                node = high;
+            } else {
+                node = low;
+            }
+        }
+        Some(iv)
+    }
+
+    /// The number of input vectors for which the output is one (1),
+    /// saturating at `u128::MAX`.
+    pub fn count(&self) -> u128 {
+        // The number of assignments of the inputs from the level of
+        // each node down which reach the terminal one (1).
+        let mut counts: Vec<u128> = Vec::with_capacity(self.nodes.len());
+        for (i, node) in self.nodes.iter().enumerate() {
+            counts.push(match i {
+                ZERO => 0,
+                ONE => 1,
+                _ => {
+                    let below = |child: usize| -> u128 {
+                        scale(counts[child], self.nodes[child].level - node.level - 1)
+                    };
+                    below(node.low).saturating_add(below(node.high))
+                }
+            });
+        }
+        scale(counts[self.root], self.nodes[self.root].level)
+    }
+}
+
+/// `count` multiplied by 2^`k`, saturating at `u128::MAX`.
+fn scale(count: u128, k: usize) -> u128 {
+    if count == 0 {
+        0
+    } else if k >= 128 {
+        u128::MAX
+    } else {
+        count.saturating_mul(1 << k)
+    }
+}
+
+/// `Manager` builds the nodes of a diagram, sharing every node between
+/// all the functions which have it and remembering the result of every
+/// operation.
+struct Manager {
+    nodes: Vec<Node>,
+    unique: BTreeMap<Node, usize>,
+    /// The conjunction (`true`) or disjunction (`false`) of two nodes.
+    applied: BTreeMap<(bool, usize, usize), usize>,
+    negated: BTreeMap<usize, usize>,
+}
+
+impl Manager {
+    fn new(inputs: usize) -> Self {
+        let terminal = |i: usize| -> Node {
+            Node {
+                level: inputs,
+                low: i,
+                high: i,
+            }
+        };
+        Manager {
+            nodes: Vec::from([terminal(ZERO), terminal(ONE)]),
+            unique: BTreeMap::new(),
+            applied: BTreeMap::new(),
+            negated: BTreeMap::new(),
+        }
+    }
+
+    /// The node testing the input at `level`, unless both its children
+    /// are the same.
+    fn node(&mut self, level: usize, low: usize, high: usize) -> usize {
+        if low == high {
+            return low;
+        }
+        let node: Node = Node { level, low, high };
+        if let Some(i) = self.unique.get(&node) {
+            return *i;
+        }
+        self.nodes.push(node);
+        self.unique.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    fn negate(&mut self, a: usize) -> usize {
+        match a {
+            ZERO => return ONE,
+            ONE => return ZERO,
+            _ => {}
+        }
+        if let Some(i) = self.negated.get(&a) {
+            return *i;
+        }
+        let Node { level, low, high } = self.nodes[a];
+        let low: usize = self.negate(low);
+        let high: usize = self.negate(high);
+        let i: usize = self.node(level, low, high);
+        self.negated.insert(a, i);
+        i
+    }
+
+    /// The conjunction of `a` and `b` if `and` is set, otherwise their
+    /// disjunction.
+    fn apply(&mut self, and: bool, a: usize, b: usize) -> usize {
+        // The constant which decides the result by itself.
+        let (absorbing, identity) = if and { (ZERO, ONE) } else { (ONE, ZERO) };
+        if a == absorbing || b == absorbing {
+            return absorbing;
+        }
+        if a == identity || a == b {
+            return b;
+        }
+        if b == identity {
+            return a;
+        }
+        let key: (bool, usize, usize) = (and, a.min(b), a.max(b));
+        if let Some(i) = self.applied.get(&key) {
+            return *i;
+        }
+        let (x, y) = (self.nodes[a], self.nodes[b]);
+        let level: usize = x.level.min(y.level);
+        let cofactors = |n: Node, i: usize| -> (usize, usize) {
+            if n.level == level {
+                (n.low, n.high)
+            } else {
+                (i, i)
+            }
+        };
+        let (a_low, a_high) = cofactors(x, a);
+        let (b_low, b_high) = cofactors(y, b);
+        let low: usize = self.apply(and, a_low, b_low);
+        let high: usize = self.apply(and, a_high, b_high);
+        let i: usize = self.node(level, low, high);
+        self.applied.insert(key, i);
+        i
+    }
+
+    /// The diagram of the node `root` alone, its nodes renumbered such
+    /// that it only depends on the function and the order.
+    fn finish(&self, order: Vec<usize>, root: usize) -> Bdd {
+        let mut indices: BTreeMap<usize, usize> = BTreeMap::from([(ZERO, ZERO), (ONE, ONE)]);
+        let mut nodes: Vec<Node> = self.nodes[..2].to_vec();
+        // Visit the low child, then the high child, then the node.
+        let mut stack: Vec<(usize, bool)> = Vec::from([(root, false)]);
+        while let Some((i, visited)) = stack.pop() {
+            if indices.contains_key(&i) {
+                continue;
+            }
+            let Node { level, low, high } = self.nodes[i];
+            if visited {
+                nodes.push(Node {
+                    level,
+                    low: indices[&low],
+                    high: indices[&high],
+                });
+                indices.insert(i, nodes.len() - 1);
+            } else {
+                stack.push((i, true));
+                stack.push((high, false));
+                stack.push((low, false));
+            }
+        }
+        Bdd {
+            order,
+            nodes,
+            root: indices[&root],
+        }
+    }
+}
+
+impl BTreeReducer<bool> {
+    /// Builds the `Bdd` of the output of the root, testing the inputs in
+    /// order; see `bdd_with_order`.
+    pub fn bdd(&self) -> Bdd {
+        let order: Vec<usize> = (0..self.input_gates().len()).collect();
+        self.bdd_with_order(&order).unwrap()
+    }
+
+    /// Builds the `Bdd` of the output of the root, testing the input
+    /// `order[0]` first, then `order[1]` and so on, where `order` must be
+    /// a permutation of the inputs. The size of the diagram may depend
+    /// greatly on the order, e.g. `(a & x) | (b & y) | (c & z)` needs
+    /// six (6) nodes in the order `a x b y c z` but fourteen (14) in the
+    /// order `a b c x y z`; related inputs are best kept together.
+    pub fn bdd_with_order(&self, order: &[usize]) -> Result<Bdd, Error> {
+        let inputs: Vec<GateId> = self.input_gates();
+        let mut levels: Vec<Option<usize>> = vec![None; inputs.len()];
+        if order.len() != inputs.len() {
+            return Err(Error::ImproperDimension);
+        }
+        for (level, j) in order.iter().enumerate() {
+            match levels.get_mut(*j) {
+                Some(l) if l.is_none() => *l = Some(level),
+                _ => return Err(Error::ImproperDimension),
+            }
+        }
+        let levels: BTreeMap<GateId, usize> = inputs
+            .into_iter()
+            .zip(levels.into_iter().flatten())
+            .collect();
+
+        let mut manager: Manager = Manager::new(order.len());
+        let mut built: BTreeMap<GateId, usize> = BTreeMap::new();
+        for id in postorder(self, &[self.root()]) {
+            let gate: &Gate<bool> = self.gate(id).unwrap();
+            let mut node: usize = match levels.get(&id) {
+                Some(level) => manager.node(*level, ZERO, ONE),
+                None => {
+                    let children: Vec<usize> = self
+                        .children(id)
+                        .into_iter()
+                        .flatten()
+                        .map(|child| -> usize { built[child] })
+                        .collect();
+                    let mut node: usize = children[0];
+                    for child in children[1..].iter() {
+                        node = manager.apply(gate.program(), node, *child);
+                    }
+                    node
+                }
+            };
+            if gate.configuration() {
+                node = manager.negate(node);
+            }
+            built.insert(id, node);
+        }
+        Ok(manager.finish(Vec::from(order), built[&self.root()]))
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::bdd::Bdd;
+    use crate::expression::parse;
+    use crate::netlist;
+    use crate::netlist::test::XOR;
+    use crate::parse::ParseError;
+    use crate::reducer::BTreeReducer;
+    use crate::truth_table::TruthTable;
+    use crate::Error;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn bdd_xor() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = netlist::parse(XOR)?;
+        let bdd: Bdd = reducer.bdd();
+        assert_eq!(bdd.inputs(), 2);
+        assert_eq!(bdd.order(), &[0, 1]);
+        assert_eq!(bdd.nodes(), 3);
+        assert!(bdd.is_satisfiable());
+        assert_eq!(bdd.count(), 2);
+        assert_eq!(bdd.satisfying_input(), Some(Vec::from([false, true])));
+        assert_eq!(bdd.evaluate(&[false, true]), Ok(true));
+        assert_eq!(bdd.evaluate(&[true, true]), Ok(false));
+        assert_eq!(bdd.evaluate(&[true]), Err(Error::ImproperDimension));
+        Ok(())
+    }
+
+    #[test]
+    fn bdd_matches_truth_table() -> Result<(), ParseError> {
+        for s in [
+            "a",
+            "!a",
+            "a & !a",
+            "a | !a",
+            "a & !b",
+            "!(a | b) & (c | !d)",
+            "a ^ b ^ c",
+            "(a & b) | !c",
+            "let x = a & b; x | !x & c",
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = parse(s)?;
+            let table: TruthTable = reducer.truth_table().unwrap();
+            let ones: usize = (0..table.rows())
+                .filter(|row| -> bool { table.get(*row) == Some(true) })
+                .count();
+            let inputs: usize = table.inputs();
+            for order in [
+                (0..inputs).collect::<Vec<usize>>(),
+                (0..inputs).rev().collect::<Vec<usize>>(),
+            ]
+            .iter()
+            {
+                let bdd: Bdd = reducer.bdd_with_order(order).unwrap();
+                assert_eq!(bdd.count(), ones as u128, "{}", s);
+                assert_eq!(bdd.is_satisfiable(), ones > 0, "{}", s);
+                for row in 0..table.rows() {
+                    assert_eq!(
+                        bdd.evaluate(&table.input(row)).ok(),
+                        table.get(row),
+                        "{}",
+                        s
+                    );
+                }
+                if let Some(iv) = bdd.satisfying_input() {
+                    assert_eq!(bdd.evaluate(&iv), Ok(true), "{}", s);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bdd_order() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse("(a & x) | (b & y) | (c & z)")?;
+        assert_eq!(reducer.bdd().nodes(), 6);
+        let bdd: Bdd = reducer.bdd_with_order(&[0, 2, 4, 1, 3, 5]).unwrap();
+        assert_eq!(bdd.nodes(), 14);
+        assert_eq!(bdd.count(), reducer.bdd().count());
+        assert_eq!(bdd.satisfying_input(), reducer.bdd().satisfying_input());
+
+        for order in [&[0, 1][..], &[0, 0, 1, 2, 3, 4], &[0, 1, 2, 3, 4, 6]].iter() {
+            assert_eq!(reducer.bdd_with_order(order), Err(Error::ImproperDimension));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bdd_equivalence() -> Result<(), ParseError> {
+        let a: BTreeReducer<bool> = parse("a & (b | c)")?;
+        assert_eq!(a.bdd(), parse("(a & b) | (a & c)")?.bdd());
+        assert_eq!(a.bdd(), parse("!(!a | !b & !c)")?.bdd());
+        assert_ne!(a.bdd(), parse("a & b | c")?.bdd());
+        let order: [usize; 3] = [2, 0, 1];
+        assert_eq!(
+            a.bdd_with_order(&order),
+            parse("let x = a & b; x | a & !!c")?.bdd_with_order(&order)
+        );
+        assert_ne!(a.bdd(), a.bdd_with_order(&order).unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn bdd_many_inputs() -> Result<(), ParseError> {
+        let names: Vec<String> = (0..100).map(|j| -> String { format!("x{}", j) }).collect();
+        let product: Bdd = parse(&names.join(" & "))?.bdd();
+        assert_eq!(product.nodes(), 100);
+        assert_eq!(product.count(), 1);
+        assert_eq!(product.satisfying_input(), Some(Vec::from([true; 100])));
+        let sum: Bdd = parse(&names.join(" | "))?.bdd();
+        assert_eq!(sum.count(), (1 << 100) - 1);
+        assert!(sum.satisfying_input().unwrap()[99]);
+        let names: Vec<String> = (0..130).map(|j| -> String { format!("x{}", j) }).collect();
+        assert_eq!(parse(&names.join(" | "))?.bdd().count(), u128::MAX);
+        Ok(())
+    }
+}
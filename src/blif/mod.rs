@@ -0,0 +1,515 @@
+//! Berkeley Logic Interchange Format (BLIF) for `BTreeReducer<bool>`.
+//!
+//! ```text
+//! .model xor_gate
+//! .inputs a b
+//! .outputs and
+//! .names a b or
+//! 1- 1
+//! -1 1
+//! .names a b nand
+//! 11 0
+//! .names or nand and
+//! 11 1
+//! .end
+//! ```
+//!
+//! Only combinational models are supported, i.e. `.model`, `.inputs`,
+//! `.outputs`, `.names` and `.end`. Every `.names` statement defines its
+//! last signal by a cover of the others: each following row is a cube of
+//! `0`, `1` or `-` per input along with the output of every row, the
+//! signal being the OR of the cubes if that output is one (1) and its
+//! complement if it is zero (0). Signals may be defined in any order,
+//! everything following a `#` is a comment and a line ending in `\`
+//! continues on the next.
+
+use crate::graph::{names, postorder};
+use crate::parse::{
+    connect, duplicate, invalid, tokenize, unexpected, ParseError, ParseErrorKind, Scaffold, Token,
+};
+use crate::reducer::api::{AddOutput, Configuration, Program, Reconfigure, Reprogram};
+use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+mod test;
+
+/// `Symbol` is a single token of a statement along with its one (1)
+/// indexed line and column; a statement may span several lines.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct Symbol<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+/// `Cover` is the function of a signal defined by `.names`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct Cover<'a> {
+    symbol: Symbol<'a>,
+    inputs: Vec<Symbol<'a>>,
+    rows: Vec<Vec<Option<bool>>>,
+    // The output of every row; a cover without rows is zero (0).
+    value: Option<bool>,
+}
+
+/// Parses a BLIF model into a `BTreeReducer<bool>`. Every input is a
+/// single input gate labeled by its name, ordered as in `.inputs`, and
+/// every signal is a gate labeled by its name. The root computes the
+/// first output and every other output is an output gate of its own.
+///
+/// A reducer always has an input and an output, and so must the model.
+/// Any input or output which is not otherwise connected to the root
+/// hangs beneath a gate whose output is the identity of the root, e.g.
+/// `x | !x | ...` beneath a series (AND) root.
+pub fn parse(s: &str) -> Result<BTreeReducer<bool>, ParseError> {
+    let mut inputs: Vec<Symbol<'_>> = Vec::new();
+    let mut outputs: Vec<Symbol<'_>> = Vec::new();
+    let mut covers: BTreeMap<&str, Cover<'_>> = BTreeMap::new();
+    // The signal whose rows follow, if any.
+    let mut cover: Option<&str> = None;
+    let mut model: bool = false;
+    let mut end: bool = false;
+    let mut lines: usize = 0;
+    for statement in statements(s, &mut lines) {
+        let keyword: Symbol<'_> = statement[0];
+        if end || (model && keyword.text == ".model") {
+            return Err(unexpected(keyword.line, keyword.column, keyword.text));
+        }
+        if keyword.text.starts_with('.') {
+            cover = None;
+        }
+        match keyword.text {
+            ".model" => {
+                if let Some(s) = statement.get(2) {
+                    return Err(unexpected(s.line, s.column, s.text));
+                }
+            }
+            ".inputs" | ".outputs" | ".names" => {
+                for symbol in statement[1..].iter() {
+                    if keyword.text == ".outputs" {
+                        outputs.push(*symbol);
+                        continue;
+                    }
+                    // Only the last signal of `.names` is defined.
+                    if keyword.text == ".names" && symbol != statement.last().unwrap() {
+                        continue;
+                    }
+                    let defined: bool = inputs.iter().any(|i| -> bool { i.text == symbol.text })
+                        || covers.contains_key(symbol.text);
+                    if defined {
+                        return Err(duplicate(symbol.line, symbol.column, symbol.text));
+                    }
+                    if keyword.text == ".inputs" {
+                        inputs.push(*symbol);
+                    }
+                }
+                if keyword.text == ".names" {
+                    let symbol: Symbol<'_> = match statement.last() {
+                        Some(s) if statement.len() > 1 => *s,
+                        _ => return Err(unexpected_end(&statement)),
+                    };
+                    covers.insert(
+                        symbol.text,
+                        Cover {
+                            symbol,
+                            inputs: statement[1..statement.len() - 1].to_vec(),
+                            rows: Vec::new(),
+                            value: None,
+                        },
+                    );
+                    cover = Some(symbol.text);
+                }
+            }
+            ".end" => {
+                if let Some(s) = statement.get(1) {
+                    return Err(unexpected(s.line, s.column, s.text));
+                }
+                end = true;
+            }
+            _ if keyword.text.starts_with('.') => {
+                return Err(unexpected(keyword.line, keyword.column, keyword.text))
+            }
+            _ => {
+                let c: &mut Cover<'_> = match cover {
+                    Some(c) => covers.get_mut(c).unwrap(),
+                    None => return Err(unexpected(keyword.line, keyword.column, keyword.text)),
+                };
+                parse_row(c, &statement)?;
+            }
+        }
+        model = true;
+    }
+    if inputs.is_empty() || outputs.is_empty() {
+        return Err(ParseError::new(lines + 1, 1, ParseErrorKind::UnexpectedEnd));
+    }
+    check(&inputs, &outputs, &covers)?;
+
+    let (scaffold, ids): (Scaffold, Vec<GateId>) =
+        Scaffold::new(inputs.iter().map(|i| -> Option<&str> { Some(i.text) }));
+    let mut builder: Builder<'_> = Builder {
+        scaffold,
+        covers,
+        signals: BTreeMap::new(),
+        inverters: BTreeMap::new(),
+        constant: inputs[0].text,
+    };
+    for (input, id) in inputs.iter().zip(ids) {
+        builder.signals.insert(input.text, id);
+    }
+    let root: GateId = builder.scaffold.reducer.root();
+    // The root may only compute the first output itself if no other
+    // signal refers to it, otherwise it passes that signal through.
+    let first: &str = outputs[0].text;
+    let referenced: bool = builder
+        .covers
+        .values()
+        .any(|c| -> bool { c.inputs.iter().any(|i| -> bool { i.text == first }) });
+    let mut gate: Gate<bool> = builder.scaffold.reducer.gate(root).unwrap().clone();
+    if builder.covers.contains_key(first) && !referenced {
+        gate.relabel(first);
+        builder.scaffold.reducer.update(root, gate).unwrap();
+        builder.define(first, root);
+        builder.signals.insert(first, root);
+    } else {
+        gate.reprogram(true).unwrap();
+        builder.scaffold.reducer.update(root, gate).unwrap();
+        builder.attach(first, root);
+    }
+    for output in outputs.iter().skip(1) {
+        let id: GateId = match builder.signals.get(output.text) {
+            Some(id) => *id,
+            None => builder.attach(output.text, builder.scaffold.sink()),
+        };
+        builder.scaffold.reducer.add_output(id).unwrap();
+    }
+    if let Some(sink) = builder.scaffold.prune() {
+        builder.literal(inputs[0].text, false, sink);
+        builder.literal(inputs[0].text, true, sink);
+    }
+    Ok(builder.scaffold.finish())
+}
+
+/// Prints a `BTreeReducer<bool>` as a BLIF model named `model` which
+/// `parse` accepts. Every gate on which an output depends is a `.names`
+/// statement of a single row, or one row per child for a parallel (OR)
+/// gate, whose output is the complement of its configuration. Signals
+/// are named as by `netlist::print`; an input gate which is configured
+/// is a signal of its own, named as the input followed by underscores.
+pub fn print(reducer: &BTreeReducer<bool>, model: &str) -> String {
+    let names: BTreeMap<GateId, String> = names(reducer);
+    let mut taken: BTreeSet<String> = names.values().cloned().collect();
+    let inputs: Vec<GateId> = reducer.input_gates();
+    let outputs: Vec<GateId> = reducer.outputs();
+    let mut signals: BTreeMap<GateId, String> = BTreeMap::new();
+    let mut covers: String = String::new();
+    for id in postorder(reducer, &outputs).iter() {
+        let gate: &Gate<bool> = reducer.gate(*id).unwrap();
+        let leaf: bool = inputs.contains(id);
+        let operands: Vec<String> = if leaf {
+            Vec::from([names[id].clone()])
+        } else {
+            reducer
+                .children(*id)
+                .into_iter()
+                .flatten()
+                .map(|child| -> String { signals[child].clone() })
+                .collect()
+        };
+        // A gate which passes its only input through is that input,
+        // unless it is an output which must be named.
+        let buffer: bool = operands.len() == 1 && !gate.configuration();
+        if buffer && (leaf || !outputs.contains(id)) {
+            signals.insert(*id, operands[0].clone());
+            continue;
+        }
+        let signal: String = if leaf {
+            let mut name: String = names[id].clone();
+            while taken.contains(&name) {
+                name.push('_');
+            }
+            taken.insert(name.clone());
+            name
+        } else {
+            names[id].clone()
+        };
+        covers.push_str(&format!(".names {} {}\n", operands.join(" "), signal));
+        let output: u8 = !gate.configuration() as u8;
+        if gate.program() || operands.len() == 1 {
+            covers.push_str(&format!("{} {}\n", "1".repeat(operands.len()), output));
+        } else {
+            for i in 0..operands.len() {
+                let row: String = (0..operands.len())
+                    .map(|j| -> char {
+                        if i == j {
+                            '1'
+                        } else {
+                            '-'
+                        }
+                    })
+                    .collect();
+                covers.push_str(&format!("{} {}\n", row, output));
+            }
+        }
+        signals.insert(*id, signal);
+    }
+    let mut s: String = format!(".model {}\n", model);
+    let inputs: Vec<&str> = inputs.iter().map(|id| -> &str { &names[id] }).collect();
+    s.push_str(&format!(".inputs {}\n", inputs.join(" ")));
+    let outputs: Vec<&str> = outputs.iter().map(|id| -> &str { &signals[id] }).collect();
+    s.push_str(&format!(".outputs {}\n", outputs.join(" ")));
+    s.push_str(&covers);
+    s.push_str(".end\n");
+    s
+}
+
+/// Splits `s` into statements of symbols, joining every line which ends
+/// in `\` with the next and skipping those which are empty. The number
+/// of lines is stored in `lines`.
+fn statements<'a>(s: &'a str, lines: &mut usize) -> Vec<Vec<Symbol<'a>>> {
+    let mut statements: Vec<Vec<Symbol<'a>>> = Vec::new();
+    let mut statement: Vec<Symbol<'a>> = Vec::new();
+    for (n, line) in s.lines().enumerate() {
+        *lines = n + 1;
+        let tokens: Vec<Token<'a>> = tokenize(line, '#');
+        let continued: bool = tokens
+            .last()
+            .is_some_and(|t| -> bool { t.text.ends_with('\\') });
+        for (i, t) in tokens.iter().enumerate() {
+            let text: &str = if continued && i + 1 == tokens.len() {
+                &t.text[..t.text.len() - 1]
+            } else {
+                t.text
+            };
+            if !text.is_empty() {
+                statement.push(Symbol {
+                    line: n + 1,
+                    column: t.column,
+                    text,
+                });
+            }
+        }
+        if !continued && !statement.is_empty() {
+            statements.push(core::mem::take(&mut statement));
+        }
+    }
+    if !statement.is_empty() {
+        statements.push(statement);
+    }
+    statements
+}
+
+/// Adds a row of `statement` to `cover`.
+fn parse_row(cover: &mut Cover<'_>, statement: &[Symbol<'_>]) -> Result<(), ParseError> {
+    let (plane, output): (Option<Symbol<'_>>, Symbol<'_>) = match (cover.inputs.len(), statement) {
+        (0, [output]) => (None, *output),
+        (0, [_, s, ..]) => return Err(unexpected(s.line, s.column, s.text)),
+        (_, [plane, output]) => (Some(*plane), *output),
+        (_, [_]) => return Err(unexpected_end(statement)),
+        (_, [_, _, s, ..]) => return Err(unexpected(s.line, s.column, s.text)),
+        (_, []) => unreachable!(),
+    };
+    let mut row: Vec<Option<bool>> = Vec::new();
+    if let Some(plane) = plane {
+        for c in plane.text.chars() {
+            row.push(match c {
+                '0' => Some(false),
+                '1' => Some(true),
+                '-' => None,
+                _ => return Err(invalid(plane.line, plane.column, plane.text)),
+            });
+        }
+        if row.len() != cover.inputs.len() {
+            return Err(invalid(plane.line, plane.column, plane.text));
+        }
+    }
+    let value: bool = match output.text {
+        "0" => false,
+        "1" => true,
+        _ => return Err(invalid(output.line, output.column, output.text)),
+    };
+    // Every row of a cover must have the same output.
+    if cover.value.is_some() && cover.value != Some(value) {
+        return Err(invalid(output.line, output.column, output.text));
+    }
+    cover.value = Some(value);
+    cover.rows.push(row);
+    Ok(())
+}
+
+/// Ensures that every signal on which an output depends is defined and
+/// that no signal depends on itself.
+fn check(
+    inputs: &[Symbol<'_>],
+    outputs: &[Symbol<'_>],
+    covers: &BTreeMap<&str, Cover<'_>>,
+) -> Result<(), ParseError> {
+    let defined = |s: &Symbol<'_>| -> Result<(), ParseError> {
+        if inputs.iter().any(|i| -> bool { i.text == s.text }) || covers.contains_key(s.text) {
+            Ok(())
+        } else {
+            Err(ParseError::new(
+                s.line,
+                s.column,
+                ParseErrorKind::UnknownName(String::from(s.text)),
+            ))
+        }
+    };
+    // A signal is finished once every signal it depends on is; one
+    // which is reached again before it is finished is on a cycle.
+    let mut finished: BTreeSet<&str> = BTreeSet::new();
+    let mut started: BTreeSet<&str> = BTreeSet::new();
+    for output in outputs.iter() {
+        defined(output)?;
+        let mut stack: Vec<(&str, bool)> = Vec::from([(output.text, false)]);
+        while let Some((name, expanded)) = stack.pop() {
+            let cover: &Cover<'_> = match covers.get(name) {
+                Some(c) => c,
+                None => continue,
+            };
+            if expanded {
+                finished.insert(name);
+                continue;
+            }
+            if finished.contains(name) {
+                continue;
+            }
+            if !started.insert(name) {
+                return Err(ParseError::new(
+                    cover.symbol.line,
+                    cover.symbol.column,
+                    ParseErrorKind::InvalidEdge,
+                ));
+            }
+            stack.push((name, true));
+            for input in cover.inputs.iter() {
+                defined(input)?;
+                if !finished.contains(input.text) {
+                    stack.push((input.text, false));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn unexpected_end(statement: &[Symbol<'_>]) -> ParseError {
+    let last: &Symbol<'_> = statement.last().unwrap();
+    ParseError::new(
+        last.line,
+        last.column + last.text.chars().count(),
+        ParseErrorKind::UnexpectedEnd,
+    )
+}
+
+struct Builder<'a> {
+    scaffold: Scaffold,
+    covers: BTreeMap<&'a str, Cover<'a>>,
+    // The gate of every input and of every signal built so far.
+    signals: BTreeMap<&'a str, GateId>,
+    // The inverting gate above every signal which has one.
+    inverters: BTreeMap<&'a str, GateId>,
+    // The input of which every constant is built, i.e. x & !x.
+    constant: &'a str,
+}
+
+impl<'a> Builder<'a> {
+    /// Adds the gate which computes `name` beneath `parent`, unless it
+    /// has been built already, returning that gate.
+    fn attach(&mut self, name: &'a str, parent: GateId) -> GateId {
+        if let Some(id) = self.signals.get(name).cloned() {
+            connect(&mut self.scaffold.reducer, parent, id);
+            return id;
+        }
+        let id: GateId = self
+            .scaffold
+            .reducer
+            .add_gate_with(GateBuilder::new(parent).label(name))
+            .unwrap();
+        self.define(name, id);
+        self.signals.insert(name, id);
+        id
+    }
+
+    /// Adds `name`, inverted if `inverted` is set, beneath `parent`.
+    fn literal(&mut self, name: &'a str, inverted: bool, parent: GateId) {
+        if !inverted {
+            self.attach(name, parent);
+            return;
+        }
+        match self.inverters.get(name).cloned() {
+            Some(id) => connect(&mut self.scaffold.reducer, parent, id),
+            None => {
+                let id: GateId = self
+                    .scaffold
+                    .reducer
+                    .add_gate_with(GateBuilder::new(parent).program(true).configuration(true))
+                    .unwrap();
+                self.attach(name, id);
+                self.inverters.insert(name, id);
+            }
+        }
+    }
+
+    /// Shapes `id` as the gate which computes the cover of `name` and
+    /// adds its children.
+    fn define(&mut self, name: &'a str, id: GateId) {
+        let cover: Cover<'a> = self.covers[name].clone();
+        let invert: bool = cover.value == Some(false);
+        let terms: Vec<Vec<(&'a str, bool)>> = cover
+            .rows
+            .iter()
+            .map(|row| -> Vec<(&'a str, bool)> {
+                row.iter()
+                    .zip(cover.inputs.iter())
+                    .filter_map(|(bit, s)| -> Option<(&'a str, bool)> {
+                        bit.map(|b| -> (&'a str, bool) { (s.text, !b) })
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut gate: Gate<bool> = self.scaffold.reducer.gate(id).unwrap().clone();
+        if terms.is_empty() || terms.iter().any(|t| -> bool { t.is_empty() }) {
+            // A cover without rows is zero (0) and one with an empty
+            // cube is one (1); either is x & !x, configured to match.
+            gate.reprogram(true).unwrap();
+            gate.reconfigure(terms.is_empty() == invert).unwrap();
+            self.scaffold.reducer.update(id, gate).unwrap();
+            self.literal(self.constant, false, id);
+            self.literal(self.constant, true, id);
+        } else if terms.len() == 1 && terms[0].len() == 1 {
+            let (s, inverted): (&'a str, bool) = terms[0][0];
+            gate.reprogram(true).unwrap();
+            gate.reconfigure(invert != inverted).unwrap();
+            self.scaffold.reducer.update(id, gate).unwrap();
+            self.attach(s, id);
+        } else if terms.len() == 1 {
+            gate.reprogram(true).unwrap();
+            gate.reconfigure(invert).unwrap();
+            self.scaffold.reducer.update(id, gate).unwrap();
+            for (s, inverted) in terms[0].iter() {
+                self.literal(s, *inverted, id);
+            }
+        } else {
+            gate.reprogram(false).unwrap();
+            gate.reconfigure(invert).unwrap();
+            self.scaffold.reducer.update(id, gate).unwrap();
+            for term in terms.iter() {
+                if let [(s, inverted)] = term.as_slice() {
+                    self.literal(s, *inverted, id);
+                    continue;
+                }
+                let t: GateId = self
+                    .scaffold
+                    .reducer
+                    .add_gate_with(GateBuilder::new(id).program(true))
+                    .unwrap();
+                for (s, inverted) in term.iter() {
+                    self.literal(s, *inverted, t);
+                }
+            }
+        }
+    }
+}
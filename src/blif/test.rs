@@ -0,0 +1,232 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::blif::{parse, print};
+    use crate::expression;
+    use crate::netlist;
+    use crate::netlist::test::XOR;
+    use crate::parse::{ParseError, ParseErrorKind};
+    use crate::reducer::api::{Output, Reinput};
+    use crate::reducer::BTreeReducer;
+    use crate::truth_table::TruthTable;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// Every output for every input vector, transitioning a clone of
+    /// `reducer`.
+    fn outputs(reducer: &BTreeReducer<bool>) -> Vec<Vec<bool>> {
+        let inputs: usize = reducer.input_gates().len();
+        (0..1 << inputs)
+            .map(|row| -> Vec<bool> {
+                let mut reducer: BTreeReducer<bool> = reducer.clone();
+                reducer
+                    .reinput(
+                        TruthTable::from_fn(inputs, |_| -> bool { false })
+                            .unwrap()
+                            .input(row),
+                    )
+                    .unwrap();
+                reducer.output()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn print_xor() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = netlist::parse(XOR)?;
+        assert_eq!(
+            print(&reducer, "xor_gate"),
+            concat!(
+                ".model xor_gate\n",
+                ".inputs a b\n",
+                ".outputs and\n",
+                ".names a b or\n",
+                "1- 1\n",
+                "-1 1\n",
+                ".names a b nand\n",
+                "11 0\n",
+                ".names or nand and\n",
+                "11 1\n",
+                ".end\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_model() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse(&print(&netlist::parse(XOR)?, "xor_gate"))?;
+        assert_eq!(reducer.gates().len(), 5);
+        assert_eq!(
+            reducer.truth_table().unwrap(),
+            TruthTable::from_fn(2, |iv: Vec<bool>| -> bool { iv[0] != iv[1] }).unwrap()
+        );
+
+        // Signals are defined out of order, with don't cares, constants
+        // and comments, across continued lines.
+        let reducer: BTreeReducer<bool> = parse(
+            "# A full adder.
+            .model full_adder
+            .inputs a b \\
+                cin unused
+            .outputs sum cout one zero a
+            .names a b cin sum
+            100 1
+            010 1
+            001 1
+            111 1
+            .names a b cin cout # majority
+            11- 1
+            1-1 1
+            -11 1
+            .names one
+            1
+            .names zero
+            .end",
+        )?;
+        let names: Vec<Option<&str>> = reducer
+            .input_gates()
+            .into_iter()
+            .map(|id| -> Option<&str> { reducer.gate(id).unwrap().label() })
+            .collect();
+        assert_eq!(
+            names,
+            Vec::from([Some("a"), Some("b"), Some("cin"), Some("unused")])
+        );
+        let expected: Vec<Vec<bool>> = (0..16)
+            .map(|row: usize| -> Vec<bool> {
+                let bits: u32 = (row & 7).count_ones();
+                Vec::from([bits % 2 == 1, bits >= 2, true, false, row & 1 == 1])
+            })
+            .collect();
+        assert_eq!(outputs(&reducer), expected);
+
+        // The first output is an input or is referred to by another
+        // signal.
+        for s in [
+            ".inputs a b\n.outputs a\n.names a b x\n11 1\n",
+            ".inputs a b\n.outputs x y\n.names a b x\n11 1\n.names x y\n0 1\n",
+            ".inputs a b\n.outputs x x\n.names a b x\n11 0\n",
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = parse(s)?;
+            let round_trip: BTreeReducer<bool> = parse(&print(&reducer, "m"))?;
+            assert_eq!(outputs(&round_trip), outputs(&reducer), "{}", s);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), ParseError> {
+        let mut reducers: Vec<BTreeReducer<bool>> = Vec::from([
+            netlist::parse(XOR)?,
+            netlist::parse(&(String::from(XOR) + "output nand\noutput a\noutput or")).unwrap(),
+        ]);
+        for s in [
+            "a",
+            "!a",
+            "a & !b",
+            "!(a | b) & (c | !d)",
+            "a ^ b ^ c",
+            "let x = a & b; x | !x & c",
+        ]
+        .iter()
+        {
+            reducers.push(expression::parse(s)?);
+        }
+        for reducer in reducers.iter() {
+            let printed: String = print(reducer, "m");
+            let parsed: BTreeReducer<bool> = parse(&printed)?;
+            assert_eq!(outputs(&parsed), outputs(reducer), "{}", printed);
+            // Printing is stable once every input has a signal of its own.
+            assert_eq!(
+                print(&parse(&print(&parsed, "m"))?, "m"),
+                print(&parsed, "m")
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (s, line, column, kind) in [
+            ("", 1, 1, ParseErrorKind::UnexpectedEnd),
+            (".inputs a\n", 2, 1, ParseErrorKind::UnexpectedEnd),
+            (
+                ".inputs a\n.outputs y\n.latch a y\n",
+                3,
+                1,
+                ParseErrorKind::UnexpectedToken(String::from(".latch")),
+            ),
+            (
+                ".inputs a\n.outputs y\n11 1\n",
+                3,
+                1,
+                ParseErrorKind::UnexpectedToken(String::from("11")),
+            ),
+            (
+                ".inputs a a\n.outputs a\n",
+                1,
+                11,
+                ParseErrorKind::DuplicateName(String::from("a")),
+            ),
+            (
+                ".inputs a\n.outputs y\n.names a y\n1 1\n.names a y\n",
+                5,
+                10,
+                ParseErrorKind::DuplicateName(String::from("y")),
+            ),
+            (
+                ".inputs a\n.outputs y\n.names a y\n2 1\n",
+                4,
+                1,
+                ParseErrorKind::InvalidValue(String::from("2")),
+            ),
+            (
+                ".inputs a b\n.outputs y\n.names a b y\n1 1\n",
+                4,
+                1,
+                ParseErrorKind::InvalidValue(String::from("1")),
+            ),
+            (
+                ".inputs a\n.outputs y\n.names a y\n1 1\n0 0\n",
+                5,
+                3,
+                ParseErrorKind::InvalidValue(String::from("0")),
+            ),
+            (
+                ".inputs a\n.outputs y\n.names a y\n1\n",
+                4,
+                2,
+                ParseErrorKind::UnexpectedEnd,
+            ),
+            (
+                ".inputs a\n.outputs y\n.names a x y\n11 1\n",
+                3,
+                10,
+                ParseErrorKind::UnknownName(String::from("x")),
+            ),
+            (
+                ".inputs a\n.outputs y\n.names a x y\n11 1\n.names y x\n1 1\n",
+                3,
+                12,
+                ParseErrorKind::InvalidEdge,
+            ),
+            (
+                ".inputs a\n.outputs a\n.end\n.names a y\n",
+                4,
+                1,
+                ParseErrorKind::UnexpectedToken(String::from(".names")),
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                parse(s),
+                Err(ParseError::new(*line, *column, kind.clone())),
+                "{}",
+                s
+            );
+        }
+    }
+}
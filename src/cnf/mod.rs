@@ -0,0 +1,121 @@
+use crate::reducer::api::{Configuration, Program};
+use crate::reducer::{BTreeReducer, GateId};
+use crate::Error;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+
+mod test;
+
+/// `Cnf` is the Tseitin encoding of a `BTreeReducer<bool>` as a formula
+/// in conjunctive normal form, such that the formula is satisfiable
+/// exactly by the outputs of every gate for some input vector. Every
+/// gate has a variable, numbered from one (1) in order of `GateId`,
+/// which stands for its output; the input of an input gate is thus its
+/// variable XORed with its configuration. Every clause is a disjunction
+/// of literals, a literal being a variable or its negation.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Cnf {
+    gates: Vec<GateId>,
+    variables: BTreeMap<GateId, usize>,
+    clauses: Vec<Vec<isize>>,
+}
+
+impl Cnf {
+    /// The number of variables, i.e. of gates.
+    pub fn variables(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// The clauses, each a slice of non-zero literals.
+    pub fn clauses(&self) -> &[Vec<isize>] {
+        &self.clauses
+    }
+
+    /// The variable of the gate `id`, if it exists.
+    pub fn variable(&self, id: GateId) -> Option<usize> {
+        self.variables.get(&id).cloned()
+    }
+
+    /// The gate of the variable `v`, if it exists.
+    pub fn gate(&self, v: usize) -> Option<GateId> {
+        self.gates.get(v.checked_sub(1)?).cloned()
+    }
+
+    /// Adds a unit clause asserting that the output of the gate `id`
+    /// equals `value`.
+    pub fn assert(&mut self, id: GateId, value: bool) -> Result<(), Error> {
+        let v: isize = self.variable(id).ok_or(Error::VertexDoesNotExist)? as isize;
+        self.clauses.push(Vec::from([if value { v } else { -v }]));
+        Ok(())
+    }
+}
+
+/// Prints the formula in the DIMACS format, preceded by a comment
+/// mapping every variable to its gate, e.g. `c 3 g2`.
+impl fmt::Display for Cnf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, id) in self.gates.iter().enumerate() {
+            writeln!(f, "c {} g{}", i + 1, id)?;
+        }
+        writeln!(f, "p cnf {} {}", self.variables(), self.clauses.len())?;
+        for clause in self.clauses.iter() {
+            for literal in clause.iter() {
+                write!(f, "{} ", literal)?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+impl BTreeReducer<bool> {
+    /// Encodes the reducer as a `Cnf`, asserting that the root outputs
+    /// `root` if it is set. The formula has a variable per gate and, for
+    /// every gate with `n` children, `n + 1` clauses relating its output
+    /// to theirs; e.g. the output `y` of a series (AND) gate with the
+    /// children `a` and `b` is encoded as `(!y | a) & (!y | b) & (y | !a
+    /// | !b)`, and `y` is negated if the gate is configured.
+    pub fn cnf(&self, root: Option<bool>) -> Cnf {
+        let gates: Vec<GateId> = self.gates().iter().map(|g| -> GateId { g.id() }).collect();
+        let variables: BTreeMap<GateId, usize> = gates
+            .iter()
+            .enumerate()
+            .map(|(i, id)| -> (GateId, usize) { (*id, i + 1) })
+            .collect();
+        let mut clauses: Vec<Vec<isize>> = Vec::new();
+        for gate in self.gates() {
+            let children: Vec<isize> = self
+                .children(gate.id())
+                .into_iter()
+                .flatten()
+                .map(|child| -> isize { variables[child] as isize })
+                .collect();
+            if children.is_empty() {
+                continue;
+            }
+            // A parallel (OR) gate is the series (AND) gate of its
+            // negated children, negated.
+            let sign: isize = if gate.program() { 1 } else { -1 };
+            let mut y: isize = variables[&gate.id()] as isize * sign;
+            if gate.configuration() {
+                y = -y;
+            }
+            let mut all: Vec<isize> = Vec::from([y]);
+            for x in children.iter() {
+                clauses.push(Vec::from([-y, x * sign]));
+                all.push(-x * sign);
+            }
+            clauses.push(all);
+        }
+        let mut cnf: Cnf = Cnf {
+            gates,
+            variables,
+            clauses,
+        };
+        if let Some(value) = root {
+            cnf.assert(self.root(), value).unwrap();
+        }
+        cnf
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::cnf::Cnf;
+    use crate::expression::parse;
+    use crate::netlist;
+    use crate::netlist::test::XOR;
+    use crate::parse::ParseError;
+    use crate::reducer::api::Configuration;
+    use crate::reducer::{BTreeReducer, GateId};
+    use crate::truth_table::TruthTable;
+    use alloc::collections::BTreeSet;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    /// The input vectors of every assignment which satisfies `cnf`.
+    fn models(reducer: &BTreeReducer<bool>, cnf: &Cnf) -> BTreeSet<Vec<bool>> {
+        let mut models: BTreeSet<Vec<bool>> = BTreeSet::new();
+        for assignment in 0..1usize << cnf.variables() {
+            let value = |l: isize| -> bool { (assignment >> (l.unsigned_abs() - 1)) & 1 == 1 };
+            let satisfied: bool = cnf
+                .clauses()
+                .iter()
+                .all(|clause| -> bool { clause.iter().any(|l| -> bool { value(*l) == (*l > 0) }) });
+            if satisfied {
+                models.insert(
+                    reducer
+                        .input_gates()
+                        .into_iter()
+                        .map(|id| -> bool {
+                            let v: usize = cnf.variable(id).unwrap();
+                            value(v as isize) != reducer.gate(id).unwrap().configuration()
+                        })
+                        .collect(),
+                );
+            }
+        }
+        models
+    }
+
+    #[test]
+    fn print_xor() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = netlist::parse(XOR)?;
+        let cnf: Cnf = reducer.cnf(Some(true));
+        assert_eq!(cnf.variables(), 5);
+        assert_eq!(cnf.variable(GateId(2)), Some(3));
+        assert_eq!(cnf.gate(3), Some(GateId(2)));
+        assert_eq!(cnf.gate(0), None);
+        assert_eq!(cnf.gate(6), None);
+        assert_eq!(
+            cnf.to_string(),
+            concat!(
+                "c 1 g0\n",
+                "c 2 g1\n",
+                "c 3 g2\n",
+                "c 4 g3\n",
+                "c 5 g4\n",
+                "p cnf 5 10\n",
+                "-1 2 0\n",
+                "-1 3 0\n",
+                "1 -2 -3 0\n",
+                "2 -4 0\n",
+                "2 -5 0\n",
+                "-2 4 5 0\n",
+                "3 4 0\n",
+                "3 5 0\n",
+                "-3 -4 -5 0\n",
+                "1 0\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn models_match_truth_table() -> Result<(), ParseError> {
+        for s in [
+            "a",
+            "!a",
+            "a & !b",
+            "!(a | b) & (c | !d)",
+            "a ^ b ^ c",
+            "let x = a & b; x | !x & c",
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = parse(s)?;
+            let table: TruthTable = reducer.truth_table().unwrap();
+            // Without an assertion every input vector is a model.
+            assert_eq!(
+                models(&reducer, &reducer.cnf(None)).len(),
+                table.rows(),
+                "{}",
+                s
+            );
+            for value in [false, true].iter() {
+                let expected: BTreeSet<Vec<bool>> = (0..table.rows())
+                    .filter(|row| -> bool { table.get(*row) == Some(*value) })
+                    .map(|row| -> Vec<bool> { table.input(row) })
+                    .collect();
+                let cnf: Cnf = reducer.cnf(Some(*value));
+                assert_eq!(models(&reducer, &cnf), expected, "{}", s);
+            }
+        }
+
+        // Any other gate may be asserted as well, e.g. an input.
+        let reducer: BTreeReducer<bool> = parse("a | b")?;
+        let mut cnf: Cnf = reducer.cnf(Some(true));
+        cnf.assert(reducer.input_gates()[0], false).unwrap();
+        assert_eq!(
+            models(&reducer, &cnf),
+            BTreeSet::from([Vec::from([false, true])])
+        );
+        assert!(cnf.assert(GateId(100), true).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Graphviz DOT export for `BTreeReducer<bool>`.
+//!
+//! Every gate is drawn with its `GateId`, label, input, configuration
+//! and program. The root is drawn bold, every output gate with a double
+//! border and every input gate as an ellipse. The edge from the tree
+//! parent of a gate, the parent `netlist::print` would name, is solid
+//! while every other edge, i.e. every short, is dashed.
+
+use crate::graph::topological_order;
+use crate::reducer::api::{Configuration, Input, Program};
+use crate::reducer::{BTreeReducer, Gate, GateId};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+mod test;
+
+/// Prints a `BTreeReducer<bool>` as a DOT digraph.
+pub fn print(reducer: &BTreeReducer<bool>) -> String {
+    render(reducer, None)
+}
+
+/// Prints a `BTreeReducer<bool>` as a DOT digraph, filling every gate
+/// by its output for the current input vector; green for one (1) and
+/// gray for zero (0). The outputs are evaluated afresh, so the reducer
+/// need not have been resolved, and the input of every gate is drawn as
+/// evaluated rather than as stored.
+pub fn print_with_outputs(reducer: &BTreeReducer<bool>) -> String {
+    let iv: Vec<bool> = reducer.input();
+    render(reducer, Some(reducer.evaluate_gates(&iv).unwrap()))
+}
+
+fn render(reducer: &BTreeReducer<bool>, outputs: Option<BTreeMap<GateId, bool>>) -> String {
+    let tree_parents: BTreeMap<GateId, GateId> = tree_parents(reducer);
+    let output_gates: Vec<GateId> = reducer.outputs();
+    let mut s: String = String::from("digraph reducer {\n    node [shape=box];\n");
+    for gate in reducer.gates() {
+        let id: GateId = gate.id();
+        let mut attributes: Vec<String> = Vec::new();
+        let output: Option<bool> = outputs.as_ref().map(|o| -> bool { o[&id] });
+        // The input of a gate is its output unless it is configured to
+        // invert.
+        let input: bool = match output {
+            Some(output) => output != gate.configuration(),
+            None => gate.input(),
+        };
+        let name: String = match gate.label() {
+            Some(label) => format!("{}: {}", id, escape(label)),
+            None => format!("{}", id),
+        };
+        attributes.push(format!(
+            "label=\"{}\\ninput={} configuration={} program={}\"",
+            name,
+            input as u8,
+            gate.configuration() as u8,
+            gate.program() as u8
+        ));
+        if reducer.children(id).into_iter().flatten().next().is_none() {
+            attributes.push(String::from("shape=ellipse"));
+        }
+        let mut styles: Vec<&str> = Vec::new();
+        if id == reducer.root() {
+            styles.push("bold");
+        }
+        if let Some(output) = output {
+            styles.push("filled");
+            attributes.push(format!(
+                "fillcolor={}",
+                if output { "palegreen" } else { "lightgray" }
+            ));
+        }
+        if !styles.is_empty() {
+            attributes.push(format!("style=\"{}\"", styles.join(",")));
+        }
+        if output_gates.contains(&id) {
+            attributes.push(String::from("peripheries=2"));
+        }
+        s.push_str(&format!("    g{} [{}];\n", id, attributes.join(", ")));
+    }
+    for gate in reducer.gates() {
+        let id: GateId = gate.id();
+        for child in reducer.children(id).into_iter().flatten() {
+            if tree_parents.get(child) == Some(&id) {
+                s.push_str(&format!("    g{} -> g{};\n", id, child));
+            } else {
+                s.push_str(&format!("    g{} -> g{} [style=dashed];\n", id, child));
+            }
+        }
+    }
+    s.push_str("}\n");
+    s
+}
+
+/// The tree parent of every gate but the root; the earliest parent in
+/// topological order, or the parent with the lowest `GateId` for gates
+/// which are unreachable from the root.
+fn tree_parents(reducer: &BTreeReducer<bool>) -> BTreeMap<GateId, GateId> {
+    let position: BTreeMap<GateId, usize> = topological_order(reducer)
+        .into_iter()
+        .enumerate()
+        .map(|(i, id)| -> (GateId, usize) { (id, i) })
+        .collect();
+    reducer
+        .gates()
+        .into_iter()
+        .filter_map(|gate: &Gate<bool>| -> Option<(GateId, GateId)> {
+            let parents: BTreeSet<GateId> = reducer.parents(gate.id());
+            let parent: &GateId = parents
+                .iter()
+                .filter(|p| -> bool { position.contains_key(p) })
+                .min_by_key(|p| -> usize { position[p] })
+                .or_else(|| parents.iter().next())?;
+            Some((gate.id(), *parent))
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::dot::{print, print_with_outputs};
+    use crate::netlist::parse;
+    use crate::netlist::test::XOR;
+    use crate::parse::ParseError;
+    use crate::reducer::api::{Reinput, RemoveShort};
+    use crate::reducer::{BTreeReducer, GateId};
+    use alloc::string::String;
+
+    #[test]
+    fn print_xor() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse(&(String::from(XOR) + "output nand\n"))?;
+        assert_eq!(
+            print(&reducer),
+            concat!(
+                "digraph reducer {\n",
+                "    node [shape=box];\n",
+                "    g0 [label=\"0: and\\ninput=1 configuration=0 program=1\", style=\"bold\", peripheries=2];\n",
+                "    g1 [label=\"1: or\\ninput=1 configuration=0 program=0\"];\n",
+                "    g2 [label=\"2: nand\\ninput=0 configuration=1 program=1\", peripheries=2];\n",
+                "    g3 [label=\"3: a\\ninput=1 configuration=0 program=0\", shape=ellipse];\n",
+                "    g4 [label=\"4: b\\ninput=0 configuration=0 program=0\", shape=ellipse];\n",
+                "    g0 -> g1;\n",
+                "    g0 -> g2;\n",
+                "    g1 -> g3;\n",
+                "    g1 -> g4;\n",
+                "    g2 -> g3 [style=dashed];\n",
+                "    g2 -> g4 [style=dashed];\n",
+                "}\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn print_outputs() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse(&(String::from(XOR) + "output nand\n"))?;
+        let dot: String = print_with_outputs(&reducer);
+        assert!(dot.contains(
+            "g0 [label=\"0: and\\ninput=1 configuration=0 program=1\", fillcolor=palegreen, style=\"bold,filled\", peripheries=2];"
+        ));
+        assert!(dot.contains(
+            "g4 [label=\"4: b\\ninput=0 configuration=0 program=0\", shape=ellipse, fillcolor=lightgray, style=\"filled\"];"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn print_outputs_unresolved() -> Result<(), ParseError> {
+        // Once B is no longer shorted to the NAND gate, the NAND gate and
+        // the root are stale until the reducer is resolved; transitioning
+        // B re-evaluates neither of them.
+        let mut reducer: BTreeReducer<bool> = parse(&(String::from(XOR) + "output nand\n"))?;
+        let nand: GateId = reducer.gate_named("nand").unwrap();
+        let b: GateId = reducer.gate_named("b").unwrap();
+        reducer.remove_short(nand, b).unwrap();
+        reducer.reinput(String::from("11")).unwrap();
+        let dot: String = print_with_outputs(&reducer);
+        for line in [
+            "g0 [label=\"0: and\\ninput=0 configuration=0 program=1\", fillcolor=lightgray, style=\"bold,filled\", peripheries=2];",
+            "g1 [label=\"1: or\\ninput=1 configuration=0 program=0\", fillcolor=palegreen, style=\"filled\"];",
+            "g2 [label=\"2: nand\\ninput=1 configuration=1 program=1\", fillcolor=lightgray, style=\"filled\", peripheries=2];",
+            "g3 [label=\"3: a\\ninput=1 configuration=0 program=0\", shape=ellipse, fillcolor=palegreen, style=\"filled\"];",
+            "g4 [label=\"4: b\\ninput=1 configuration=0 program=0\", shape=ellipse, fillcolor=palegreen, style=\"filled\"];",
+        ]
+        .iter()
+        {
+            assert!(dot.contains(line), "{}", line);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn escape() {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let mut gate = reducer.gate(reducer.root()).unwrap().clone();
+        gate.relabel("say \"hi\"");
+        reducer.update(reducer.root(), gate).unwrap();
+        assert!(print(&reducer).contains("label=\"0: say \\\"hi\\\"\\n"));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::cnf::Cnf;
+use crate::reducer::api::{Configuration, Output, Reinput};
+use crate::reducer::{BTreeReducer, GateId};
+use crate::solver::Solver;
+use alloc::vec;
+use alloc::vec::Vec;
+
+mod test;
+
+/// The largest number of inputs for which `equivalent` simulates every
+/// input vector rather than solving a miter.
+const EXHAUSTIVE: usize = 16;
+
+/// `Counterexample` shows that two reducers do not compute the same
+/// function.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Counterexample {
+    /// The reducers have different numbers of inputs or of outputs.
+    Dimension,
+    /// The outputs of the reducers, beginning with the root, differ for
+    /// the input vector `input`, ordered as for `Reinput<Vec<bool>>`.
+    Input {
+        input: Vec<bool>,
+        a: Vec<bool>,
+        b: Vec<bool>,
+    },
+}
+
+/// Proves that the reducers `a` and `b` compute the same function, i.e.
+/// that every output of `a` equals the same output of `b` for every
+/// input vector, or finds an input vector for which they differ.
+///
+/// Reducers of at most sixteen (16) inputs are simulated for every
+/// input vector, 64 at a time. Otherwise the miter of the reducers,
+/// which is satisfiable exactly by an input vector for which some
+/// output differs, is encoded as by `cnf` and solved by the in-crate
+/// solver of `find_input`. Neither reducer is transitioned.
+pub fn equivalent(a: &BTreeReducer<bool>, b: &BTreeReducer<bool>) -> Result<(), Counterexample> {
+    let inputs: usize = a.input_gates().len();
+    if b.input_gates().len() != inputs || b.outputs().len() != a.outputs().len() {
+        return Err(Counterexample::Dimension);
+    }
+    let input: Option<Vec<bool>> = if inputs <= EXHAUSTIVE {
+        simulate(a, b)
+    } else {
+        miter(a, b)
+    };
+    match input {
+        None => Ok(()),
+        Some(input) => {
+            let output = |reducer: &BTreeReducer<bool>| -> Vec<bool> {
+                let mut reducer: BTreeReducer<bool> = reducer.clone();
+                reducer.reinput(input.clone()).unwrap();
+                reducer.output()
+            };
+            let (a, b) = (output(a), output(b));
+            debug_assert_ne!(a, b);
+            Err(Counterexample::Input { input, a, b })
+        }
+    }
+}
+
+/// The first input vector, counting as for `TruthTable`, for which some
+/// output differs, if there is one.
+fn simulate(a: &BTreeReducer<bool>, b: &BTreeReducer<bool>) -> Option<Vec<bool>> {
+    let inputs: usize = a.input_gates().len();
+    let rows: usize = 1 << inputs;
+    for base in (0..rows).step_by(64) {
+        // Bit `k` of the `j`th word is input `j` of row `base + k`.
+        let mut words: Vec<u64> = vec![0; inputs];
+        for (j, word) in words.iter_mut().enumerate() {
+            for k in 0..64 {
+                *word |= ((((base + k) >> j) & 1) as u64) << k;
+            }
+        }
+        let mask: u64 = if rows - base < 64 {
+            (1 << (rows - base)) - 1
+        } else {
+            !0
+        };
+        let differences: u64 = a
+            .evaluate_outputs_word(&words)
+            .unwrap()
+            .into_iter()
+            .zip(b.evaluate_outputs_word(&words).unwrap())
+            .fold(0, |d, (x, y)| -> u64 { d | (x ^ y) })
+            & mask;
+        if differences != 0 {
+            let row: usize = base + differences.trailing_zeros() as usize;
+            return Some(
+                (0..inputs)
+                    .map(|j| -> bool { (row >> j) & 1 == 1 })
+                    .collect(),
+            );
+        }
+    }
+    None
+}
+
+/// An input vector for which some output differs, if there is one,
+/// found by solving the miter of the reducers: their encodings side by
+/// side, their inputs equal and a variable for every output which
+/// implies that the outputs differ, one of which must hold.
+fn miter(a: &BTreeReducer<bool>, b: &BTreeReducer<bool>) -> Option<Vec<bool>> {
+    let (x, y): (Cnf, Cnf) = (a.cnf(None), b.cnf(None));
+    let offset: isize = x.variables() as isize;
+    let mut clauses: Vec<Vec<isize>> = Vec::from(x.clauses());
+    clauses.extend(y.clauses().iter().map(|clause| -> Vec<isize> {
+        clause
+            .iter()
+            .map(|l| -> isize { l + l.signum() * offset })
+            .collect()
+    }));
+    // The literal of the output of a gate of `a`, or of `b` if `offset`
+    // is set, negated if `invert` is set.
+    let literal = |cnf: &Cnf, id: GateId, offset: isize, invert: bool| -> isize {
+        let l: isize = cnf.variable(id).unwrap() as isize + offset;
+        if invert {
+            -l
+        } else {
+            l
+        }
+    };
+    for (i, j) in a.input_gates().into_iter().zip(b.input_gates()) {
+        let l: isize = literal(&x, i, 0, a.gate(i).unwrap().configuration());
+        let m: isize = literal(&y, j, offset, b.gate(j).unwrap().configuration());
+        clauses.push(Vec::from([-l, m]));
+        clauses.push(Vec::from([l, -m]));
+    }
+    let mut variables: usize = x.variables() + y.variables();
+    let mut differences: Vec<isize> = Vec::new();
+    for (i, j) in a.outputs().into_iter().zip(b.outputs()) {
+        variables += 1;
+        let d: isize = variables as isize;
+        let l: isize = literal(&x, i, 0, false);
+        let m: isize = literal(&y, j, offset, false);
+        clauses.push(Vec::from([-d, l, m]));
+        clauses.push(Vec::from([-d, -l, -m]));
+        differences.push(d);
+    }
+    clauses.push(differences);
+    let model: Vec<bool> = Solver::new(variables, &clauses).solve()?;
+    Some(
+        a.input_gates()
+            .into_iter()
+            .map(|id| -> bool {
+                model[x.variable(id).unwrap() - 1] != a.gate(id).unwrap().configuration()
+            })
+            .collect(),
+    )
+}
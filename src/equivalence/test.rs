@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::equivalence::{equivalent, miter, simulate, Counterexample};
+    use crate::expression::parse;
+    use crate::netlist;
+    use crate::netlist::test::XOR;
+    use crate::parse::ParseError;
+    use crate::reducer::BTreeReducer;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    const HALF_ADDER: &str = "
+        root sum program=1
+        gate or sum
+        gate nand sum program=1 configuration=1
+        gate a or
+        gate b or
+        short nand a
+        short nand b
+        output nand
+    ";
+
+    #[test]
+    fn equivalent_small() -> Result<(), ParseError> {
+        for (s, t) in [
+            ("a & (b | c)", "(a & b) | (a & c)"),
+            ("!(a | b)", "!a & !b"),
+            ("a ^ b ^ c", "let x = a ^ b; !x & c | x & !c"),
+            ("a | !a", "let x = a; !x | x"),
+        ]
+        .iter()
+        {
+            assert_eq!(equivalent(&parse(s)?, &parse(t)?), Ok(()), "{} {}", s, t);
+        }
+        assert_eq!(equivalent(&netlist::parse(XOR)?, &parse("a ^ b")?), Ok(()));
+
+        assert_eq!(
+            equivalent(&parse("a & (b | c)")?, &parse("a & b | c")?),
+            Err(Counterexample::Input {
+                input: Vec::from([false, false, true]),
+                a: Vec::from([false]),
+                b: Vec::from([true]),
+            })
+        );
+        assert_eq!(
+            equivalent(&parse("a & b")?, &parse("a & b & c")?),
+            Err(Counterexample::Dimension)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn equivalent_outputs() -> Result<(), ParseError> {
+        let adder: BTreeReducer<bool> = netlist::parse(HALF_ADDER)?;
+        assert_eq!(equivalent(&adder, &adder.clone()), Ok(()));
+        let wrong: BTreeReducer<bool> = netlist::parse(&HALF_ADDER.replace(
+            "gate nand sum program=1 configuration=1",
+            "gate nand sum program=0 configuration=1",
+        ))?;
+        assert_eq!(
+            equivalent(&adder, &wrong),
+            Err(Counterexample::Input {
+                input: Vec::from([true, false]),
+                a: Vec::from([true, true]),
+                b: Vec::from([false, false]),
+            })
+        );
+        assert_eq!(
+            equivalent(&adder, &netlist::parse(XOR)?),
+            Err(Counterexample::Dimension)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn miter_agrees() -> Result<(), ParseError> {
+        for (s, t) in [
+            ("a & (b | c)", "(a & b) | (a & c)"),
+            ("a & (b | c)", "a & b | c"),
+            ("!(a | b) & (c | !d)", "!a & !b & !(!c & d)"),
+            ("!(a | b) & (c | !d)", "!a & !b & !(c & d)"),
+            ("a", "!a"),
+            ("a | !a", "let x = a; !x | x"),
+        ]
+        .iter()
+        {
+            let (a, b) = (parse(s)?, parse(t)?);
+            assert_eq!(
+                miter(&a, &b).is_some(),
+                simulate(&a, &b).is_some(),
+                "{} {}",
+                s,
+                t
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn equivalent_large() -> Result<(), ParseError> {
+        let names: Vec<String> = (0..40).map(|j| -> String { format!("x{}", j) }).collect();
+        let product: BTreeReducer<bool> = parse(&names.join(" & "))?;
+        let negated: Vec<String> = names
+            .iter()
+            .map(|n| -> String { format!("!{}", n) })
+            .collect();
+        let de_morgan: BTreeReducer<bool> = parse(&format!("!({})", negated.join(" | ")))?;
+        assert_eq!(equivalent(&product, &de_morgan), Ok(()));
+
+        // The products differ only where every other input is one (1).
+        let mut literals: Vec<String> = names.clone();
+        literals[23] = format!("!{}", names[23]);
+        let other: BTreeReducer<bool> = parse(&literals.join(" & "))?;
+        let mut input: Vec<bool> = Vec::from([true; 40]);
+        match equivalent(&product, &other) {
+            Err(Counterexample::Input { input: iv, a, b }) => {
+                assert!(iv
+                    .iter()
+                    .enumerate()
+                    .all(|(j, x)| -> bool { *x || j == 23 }));
+                input[23] = iv[23];
+                assert_eq!(iv, input);
+                assert_eq!(a, Vec::from([iv[23]]));
+                assert_eq!(b, Vec::from([!iv[23]]));
+            }
+            result => panic!("{:?}", result),
+        }
+        Ok(())
+    }
+}
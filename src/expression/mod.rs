@@ -0,0 +1,561 @@
+//! Boolean expressions over named variables for `BTreeReducer<bool>`.
+//!
+//! ```text
+//! (a & b) | !c
+//! ```
+//!
+//! An expression is built from variables, parentheses and, in order
+//! of increasing precedence, the `|` (OR), `^` (XOR), `&` (AND) and
+//! `!` (NOT) operators. A variable is any name which the netlist format
+//! accepts and everything following a `#` is a comment. An expression
+//! may be preceded by any number of bindings, each of which is built
+//! once however many times it is referred to:
+//!
+//! ```text
+//! let nand = !(a & b);
+//! (a | b) & nand
+//! ```
+//!
+//! Conversely, `print` renders a reducer as such an expression and
+//! `print_bound` binds every shared gate to a name with `let` first.
+
+use crate::graph::{names, topological_order};
+use crate::parse::{connect, duplicate, invalid, is_name, unexpected, ParseError, ParseErrorKind};
+use crate::reducer::api::{Configuration, Output, Program, Reconfigure, Reprogram};
+use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+mod test;
+
+/// `Lexeme` is a single operator, parenthesis or variable name along
+/// with its one (1) indexed line and column.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct Lexeme<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+/// `Expression` is the syntax tree of a parsed expression. Chains of
+/// the same associative operator are flattened into a single node.
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum Expression<'a> {
+    Variable(&'a str),
+    Not(Box<Expression<'a>>),
+    And(Vec<Expression<'a>>),
+    Or(Vec<Expression<'a>>),
+    Xor(Box<Expression<'a>>, Box<Expression<'a>>),
+}
+
+/// Parses an expression into a `BTreeReducer<bool>`. Every variable is
+/// a single input gate labeled by its name, shorted to every gate in
+/// which it appears, so that the inputs may be transitioned with
+/// `reinput_named`. The input gates are ordered by the first appearance
+/// of each variable.
+pub fn parse(s: &str) -> Result<BTreeReducer<bool>, ParseError> {
+    let lexemes: Vec<Lexeme<'_>> = lex(s)?;
+    let mut parser: Parser<'_, '_> = Parser {
+        s,
+        lexemes: &lexemes,
+        i: 0,
+        used: BTreeSet::new(),
+        defining: None,
+        bindings: BTreeMap::new(),
+    };
+    let expression: Expression<'_> = parser.statements()?;
+    if let Some(l) = parser.peek() {
+        return Err(unexpected(l.line, l.column, l.text));
+    }
+    let mut builder: Builder<'_> = Builder {
+        reducer: BTreeReducer::new(),
+        variables: BTreeMap::new(),
+        bindings: parser.bindings,
+    };
+    let root: GateId = builder.reducer.root();
+    let mut gate: Gate<bool> = builder.reducer.gate(root).unwrap().clone();
+    let (program, configuration): (bool, bool) = shape(&expression);
+    gate.reprogram(program).unwrap();
+    gate.reconfigure(configuration).unwrap();
+    builder.reducer.update(root, gate).unwrap();
+    builder.populate(&expression, root);
+    let _: bool = builder.reducer.output();
+    Ok(builder.reducer)
+}
+
+/// Prints a `BTreeReducer<bool>` as an expression which `parse`
+/// accepts. Series gates are printed as `&`, parallel gates as `|` and
+/// inverting gates with `!`. A gate which computes the XOR, or XNOR, of
+/// two operands through a pair of gates used by nothing else, as
+/// `parse` builds `x ^ y`, is printed with `^`. A gate with several parents is printed in
+/// full wherever it appears; see `print_bound`. Input gates are named
+/// as by `netlist::print`.
+pub fn print(reducer: &BTreeReducer<bool>) -> String {
+    let names: BTreeMap<GateId, String> = names(reducer);
+    render(reducer, &names, &BTreeSet::new(), reducer.root()).0
+}
+
+/// Prints a `BTreeReducer<bool>` as an expression in which every gate
+/// with several parents, other than an input gate, is printed only once
+/// as a `let` binding ahead of any expression which refers to it, e.g.
+///
+/// ```text
+/// let g4 = a & b;
+/// (a | b) & !g4
+/// ```
+pub fn print_bound(reducer: &BTreeReducer<bool>) -> String {
+    let names: BTreeMap<GateId, String> = names(reducer);
+    let order: Vec<GateId> = topological_order(reducer);
+    let reachable: BTreeSet<GateId> = order.iter().cloned().collect();
+    // The pair of gates beneath an XOR is printed as part of it, so the
+    // operands of the XOR count it as their only parent.
+    let mut xors: BTreeMap<GateId, GateId> = BTreeMap::new();
+    for id in order.iter() {
+        if xor(reducer, *id).is_some() {
+            for child in reducer.children(*id).into_iter().flatten() {
+                xors.insert(*child, *id);
+            }
+        }
+    }
+    let bound: BTreeSet<GateId> = order
+        .iter()
+        .filter(|id| -> bool {
+            let parents: BTreeSet<GateId> = reducer
+                .parents(**id)
+                .intersection(&reachable)
+                .map(|p| -> GateId { *xors.get(p).unwrap_or(p) })
+                .collect();
+            reducer
+                .children(**id)
+                .into_iter()
+                .flatten()
+                .next()
+                .is_some()
+                && parents.len() > 1
+        })
+        .cloned()
+        .collect();
+    let mut statements: String = String::new();
+    for id in order
+        .iter()
+        .rev()
+        .filter(|id| -> bool { bound.contains(id) })
+    {
+        let (expression, _): (String, u8) = render_gate(reducer, &names, &bound, *id);
+        statements.push_str(&format!("let {} = {};\n", names[id], expression));
+    }
+    statements.push_str(&render(reducer, &names, &bound, reducer.root()).0);
+    statements
+}
+
+/// The precedence of a rendered expression; an operand is parenthesized
+/// if its precedence is lower than that of its operator.
+const OR: u8 = 0;
+const XOR: u8 = 1;
+const AND: u8 = 2;
+const ATOM: u8 = 3;
+
+/// The output of `gate` given the outputs of its children.
+fn output(gate: &Gate<bool>, children: &[bool]) -> bool {
+    let state_set: bool = children.iter().any(|c| -> bool { *c != gate.program() });
+    (state_set != gate.program()) != gate.configuration()
+}
+
+/// The operands of `id`, along with whether it is inverted, if it
+/// computes the XOR of exactly two gates through a pair of gates whose
+/// only parent is `id`, e.g. `(x | y) & !(x & y)`.
+fn xor(reducer: &BTreeReducer<bool>, id: GateId) -> Option<(GateId, GateId, bool)> {
+    let pair: Vec<GateId> = reducer.children(id)?.iter().cloned().collect();
+    if pair.len() != 2
+        || pair
+            .iter()
+            .any(|g| -> bool { reducer.parents(*g).len() != 1 })
+    {
+        return None;
+    }
+    let operands: Vec<GateId> = reducer.children(pair[0])?.iter().cloned().collect();
+    if operands.len() != 2 || reducer.children(pair[0]) != reducer.children(pair[1]) {
+        return None;
+    }
+    let gate: &Gate<bool> = reducer.gate(id)?;
+    let inner: Vec<&Gate<bool>> = pair
+        .iter()
+        .map(|g| -> &Gate<bool> { reducer.gate(*g).unwrap() })
+        .collect();
+    let f = |x: bool, y: bool| -> bool {
+        let outputs: Vec<bool> = inner
+            .iter()
+            .map(|g| -> bool { output(g, &[x, y]) })
+            .collect();
+        output(gate, &outputs)
+    };
+    match [
+        f(false, false),
+        f(true, false),
+        f(false, true),
+        f(true, true),
+    ] {
+        [false, true, true, false] => Some((operands[0], operands[1], false)),
+        [true, false, false, true] => Some((operands[0], operands[1], true)),
+        _ => None,
+    }
+}
+
+fn render(
+    reducer: &BTreeReducer<bool>,
+    names: &BTreeMap<GateId, String>,
+    bound: &BTreeSet<GateId>,
+    id: GateId,
+) -> (String, u8) {
+    if bound.contains(&id) {
+        return (names[&id].clone(), ATOM);
+    }
+    render_gate(reducer, names, bound, id)
+}
+
+/// Renders `id` as an operand of an operator of `precedence`. The
+/// operator is left associative, so a right operand of the same
+/// precedence is parenthesized as well.
+fn render_operand(
+    reducer: &BTreeReducer<bool>,
+    names: &BTreeMap<GateId, String>,
+    bound: &BTreeSet<GateId>,
+    id: GateId,
+    precedence: u8,
+    right: bool,
+) -> String {
+    match render(reducer, names, bound, id) {
+        (operand, p) if p < precedence || (right && p == precedence) => format!("({})", operand),
+        (operand, _) => operand,
+    }
+}
+
+fn render_gate(
+    reducer: &BTreeReducer<bool>,
+    names: &BTreeMap<GateId, String>,
+    bound: &BTreeSet<GateId>,
+    id: GateId,
+) -> (String, u8) {
+    let gate: &Gate<bool> = reducer.gate(id).unwrap();
+    if let Some((x, y, inverted)) = xor(reducer, id) {
+        let expression: String = format!(
+            "{} ^ {}",
+            render_operand(reducer, names, bound, x, XOR, false),
+            render_operand(reducer, names, bound, y, XOR, true)
+        );
+        return if inverted {
+            (format!("!({})", expression), ATOM)
+        } else {
+            (expression, XOR)
+        };
+    }
+    let children: Vec<GateId> = reducer
+        .children(id)
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    let (expression, precedence): (String, u8) = match children.len() {
+        0 => (names[&id].clone(), ATOM),
+        1 => render(reducer, names, bound, children[0]),
+        _ => {
+            let (operator, precedence): (&str, u8) = if gate.program() {
+                (" & ", AND)
+            } else {
+                (" | ", OR)
+            };
+            let operands: Vec<String> = children
+                .iter()
+                .map(|child| -> String {
+                    render_operand(reducer, names, bound, *child, precedence, false)
+                })
+                .collect();
+            (operands.join(operator), precedence)
+        }
+    };
+    match (gate.configuration(), precedence) {
+        (false, _) => (expression, precedence),
+        (true, ATOM) => (format!("!{}", expression), ATOM),
+        (true, _) => (format!("!({})", expression), ATOM),
+    }
+}
+
+fn lex(s: &str) -> Result<Vec<Lexeme<'_>>, ParseError> {
+    let mut lexemes: Vec<Lexeme<'_>> = Vec::new();
+    for (n, line) in s.lines().enumerate() {
+        let line: &str = match line.find('#') {
+            Some(i) => &line[..i],
+            None => line,
+        };
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let column: usize = line[..i].chars().count() + 1;
+            let end: usize = if c.is_ascii_alphanumeric() || c == '_' {
+                let mut end: usize = i + 1;
+                while let Some((j, d)) = chars.peek() {
+                    if !(d.is_ascii_alphanumeric() || *d == '_') {
+                        break;
+                    }
+                    end = j + 1;
+                    chars.next();
+                }
+                end
+            } else {
+                i + c.len_utf8()
+            };
+            let text: &str = &line[i..end];
+            match c {
+                _ if c.is_whitespace() => continue,
+                '(' | ')' | '!' | '&' | '^' | '|' | '=' | ';' => {}
+                _ if is_name(text) => {}
+                _ => return Err(invalid(n + 1, column, text)),
+            }
+            lexemes.push(Lexeme {
+                line: n + 1,
+                column,
+                text,
+            });
+        }
+    }
+    Ok(lexemes)
+}
+
+struct Parser<'a, 'b> {
+    s: &'a str,
+    lexemes: &'b [Lexeme<'a>],
+    i: usize,
+    // Every variable referred to so far, which may no longer be bound.
+    used: BTreeSet<&'a str>,
+    // The binding being parsed, which may not refer to itself.
+    defining: Option<&'a str>,
+    bindings: BTreeMap<&'a str, Expression<'a>>,
+}
+
+impl<'a, 'b> Parser<'a, 'b> {
+    fn peek(&self) -> Option<Lexeme<'a>> {
+        self.lexemes.get(self.i).cloned()
+    }
+
+    fn accept(&mut self, text: &str) -> bool {
+        if self.peek().map(|l| -> &str { l.text }) == Some(text) {
+            self.i += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the next lexeme, which must satisfy `f`.
+    fn expect<F>(&mut self, f: F) -> Result<Lexeme<'a>, ParseError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        match self.peek() {
+            Some(l) if f(l.text) => {
+                self.i += 1;
+                Ok(l)
+            }
+            Some(l) => Err(unexpected(l.line, l.column, l.text)),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    fn unexpected_end(&self) -> ParseError {
+        let line: usize = self.s.lines().count().max(1);
+        let column: usize = self.s.lines().last().unwrap_or("").chars().count() + 1;
+        ParseError::new(line, column, ParseErrorKind::UnexpectedEnd)
+    }
+
+    fn statements(&mut self) -> Result<Expression<'a>, ParseError> {
+        while self.accept("let") {
+            let name: Lexeme<'a> = self.expect(is_name)?;
+            if name.text == "let"
+                || self.used.contains(name.text)
+                || self.bindings.contains_key(name.text)
+            {
+                return Err(duplicate(name.line, name.column, name.text));
+            }
+            self.expect(|t| -> bool { t == "=" })?;
+            self.defining = Some(name.text);
+            let expression: Expression<'a> = self.or()?;
+            self.defining = None;
+            self.expect(|t| -> bool { t == ";" })?;
+            self.bindings.insert(name.text, expression);
+        }
+        self.or()
+    }
+
+    fn or(&mut self) -> Result<Expression<'a>, ParseError> {
+        let mut operands: Vec<Expression<'a>> = Vec::new();
+        loop {
+            // Parenthesized operands of the same operator are merged.
+            match self.xor()? {
+                Expression::Or(inner) => operands.extend(inner),
+                operand => operands.push(operand),
+            }
+            if !self.accept("|") {
+                break;
+            }
+        }
+        Ok(if operands.len() == 1 {
+            operands.pop().unwrap()
+        } else {
+            Expression::Or(operands)
+        })
+    }
+
+    fn xor(&mut self) -> Result<Expression<'a>, ParseError> {
+        let mut expression: Expression<'a> = self.and()?;
+        while self.accept("^") {
+            expression = Expression::Xor(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<Expression<'a>, ParseError> {
+        let mut operands: Vec<Expression<'a>> = Vec::new();
+        loop {
+            // Parenthesized operands of the same operator are merged.
+            match self.not()? {
+                Expression::And(inner) => operands.extend(inner),
+                operand => operands.push(operand),
+            }
+            if !self.accept("&") {
+                break;
+            }
+        }
+        Ok(if operands.len() == 1 {
+            operands.pop().unwrap()
+        } else {
+            Expression::And(operands)
+        })
+    }
+
+    fn not(&mut self) -> Result<Expression<'a>, ParseError> {
+        if self.accept("!") {
+            return Ok(Expression::Not(Box::new(self.not()?)));
+        }
+        let l: Lexeme<'a> = self
+            .peek()
+            .ok_or_else(|| -> ParseError { self.unexpected_end() })?;
+        self.i += 1;
+        match l.text {
+            "(" => {
+                let expression: Expression<'a> = self.or()?;
+                if !self.accept(")") {
+                    return Err(match self.peek() {
+                        Some(r) => unexpected(r.line, r.column, r.text),
+                        None => self.unexpected_end(),
+                    });
+                }
+                Ok(expression)
+            }
+            _ if self.defining == Some(l.text) => Err(ParseError::new(
+                l.line,
+                l.column,
+                ParseErrorKind::UnknownName(String::from(l.text)),
+            )),
+            _ if is_name(l.text) && l.text != "let" => {
+                self.used.insert(l.text);
+                Ok(Expression::Variable(l.text))
+            }
+            _ => Err(unexpected(l.line, l.column, l.text)),
+        }
+    }
+}
+
+/// The program and configuration of the gate which computes `e`; a
+/// single variable is computed by a series gate which passes its only
+/// child through.
+fn shape(e: &Expression<'_>) -> (bool, bool) {
+    match e {
+        Expression::Variable(_) | Expression::And(_) | Expression::Xor(_, _) => (true, false),
+        Expression::Or(_) => (false, false),
+        Expression::Not(x) => {
+            let (program, configuration): (bool, bool) = shape(x);
+            (program, !configuration)
+        }
+    }
+}
+
+struct Builder<'a> {
+    reducer: BTreeReducer<bool>,
+    // The gate of every input and of every binding built so far.
+    variables: BTreeMap<&'a str, GateId>,
+    bindings: BTreeMap<&'a str, Expression<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    /// Adds the gates which compute `e` beneath `parent`, returning the
+    /// gate whose output is `e`.
+    fn attach(&mut self, e: &Expression<'a>, parent: GateId) -> GateId {
+        if let Expression::Variable(name) = e {
+            return match self.variables.get(name).cloned() {
+                Some(id) => {
+                    connect(&mut self.reducer, parent, id);
+                    id
+                }
+                None if self.bindings.contains_key(name) => {
+                    let binding: Expression<'a> = self.bindings[name].clone();
+                    let id: GateId = self.attach(&binding, parent);
+                    if !matches!(binding, Expression::Variable(_)) {
+                        let mut gate: Gate<bool> = self.reducer.gate(id).unwrap().clone();
+                        gate.relabel(name);
+                        self.reducer.update(id, gate).unwrap();
+                    }
+                    self.variables.insert(name, id);
+                    id
+                }
+                None => {
+                    let id: GateId = self
+                        .reducer
+                        .add_gate_with(GateBuilder::new(parent).label(name))
+                        .unwrap();
+                    self.variables.insert(name, id);
+                    id
+                }
+            };
+        }
+        let (program, configuration): (bool, bool) = shape(e);
+        let id: GateId = self
+            .reducer
+            .add_gate_with(
+                GateBuilder::new(parent)
+                    .program(program)
+                    .configuration(configuration),
+            )
+            .unwrap();
+        self.populate(e, id);
+        id
+    }
+
+    /// Adds the children of `id`, which is already shaped as `e`.
+    fn populate(&mut self, e: &Expression<'a>, id: GateId) {
+        match e {
+            Expression::Variable(_) => {
+                self.attach(e, id);
+            }
+            Expression::Not(x) => self.populate(x, id),
+            Expression::And(xs) | Expression::Or(xs) => {
+                for x in xs.iter() {
+                    self.attach(x, id);
+                }
+            }
+            // x ^ y = (x | y) & !(x & y)
+            Expression::Xor(x, y) => {
+                let or: GateId = self.reducer.add_gate_with(GateBuilder::new(id)).unwrap();
+                let nand: GateId = self
+                    .reducer
+                    .add_gate_with(GateBuilder::new(id).program(true).configuration(true))
+                    .unwrap();
+                let x: GateId = self.attach(x, or);
+                let y: GateId = self.attach(y, or);
+                connect(&mut self.reducer, nand, x);
+                connect(&mut self.reducer, nand, y);
+            }
+        }
+    }
+}
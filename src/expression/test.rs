@@ -0,0 +1,287 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::expression::{parse, print, print_bound};
+    use crate::parse::{ParseError, ParseErrorKind};
+    use crate::reducer::api::{AddGate, Output, Reconfigure, Reprogram, Short};
+    use crate::reducer::{BTreeReducer, GateBuilder, GateId};
+    use crate::truth_table::TruthTable;
+    use crate::Error;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn parse_expression() -> Result<(), ParseError> {
+        let mut reducer: BTreeReducer<bool> = parse("(a & b) | !c")?;
+        assert_eq!(reducer.input_gates().len(), 3);
+        assert_eq!(
+            reducer.truth_table().unwrap(),
+            TruthTable::from_fn(3, |iv: Vec<bool>| -> bool { (iv[0] && iv[1]) || !iv[2] }).unwrap()
+        );
+
+        let mut iv: BTreeMap<&str, bool> = BTreeMap::new();
+        iv.insert("a", true);
+        iv.insert("b", false);
+        iv.insert("c", true);
+        reducer.reinput_named(&iv).unwrap();
+        let output: bool = reducer.output();
+        assert!(!output);
+        iv.insert("b", true);
+        reducer.reinput_named(&iv).unwrap();
+        let output: bool = reducer.output();
+        assert!(output);
+        Ok(())
+    }
+
+    #[test]
+    fn precedence() -> Result<(), ParseError> {
+        for (s, f) in [
+            (
+                "a",
+                (|iv: &[bool]| -> bool { iv[0] }) as fn(&[bool]) -> bool,
+            ),
+            ("!a", |iv: &[bool]| -> bool { !iv[0] }),
+            ("!!a", |iv: &[bool]| -> bool { iv[0] }),
+            ("a ^ b", |iv: &[bool]| -> bool { iv[0] != iv[1] }),
+            ("!(a ^ b)", |iv: &[bool]| -> bool { iv[0] == iv[1] }),
+            ("a | b & c", |iv: &[bool]| -> bool {
+                iv[0] || (iv[1] && iv[2])
+            }),
+            ("a ^ b | c", |iv: &[bool]| -> bool {
+                (iv[0] != iv[1]) || iv[2]
+            }),
+            ("a & b ^ c", |iv: &[bool]| -> bool {
+                (iv[0] && iv[1]) != iv[2]
+            }),
+            ("!a & b", |iv: &[bool]| -> bool { !iv[0] && iv[1] }),
+            ("a ^ b ^ c", |iv: &[bool]| -> bool { iv[0] ^ iv[1] ^ iv[2] }),
+            ("a & (b | c) & !(b & c)", |iv: &[bool]| -> bool {
+                iv[0] && (iv[1] || iv[2]) && !(iv[1] && iv[2])
+            }),
+            ("a & a", |iv: &[bool]| -> bool { iv[0] }),
+            ("a ^ a", |_: &[bool]| -> bool { false }),
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = parse(s)?;
+            let inputs: usize = reducer.input_gates().len();
+            assert_eq!(
+                reducer.truth_table().unwrap(),
+                TruthTable::from_fn(inputs, |iv: Vec<bool>| -> bool { f(&iv) }).unwrap(),
+                "{}",
+                s
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn single_leaf_per_variable() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse(
+            "# Every variable appears several times.
+            (x & y) | (x & z) | (y ^ z)
+            | !x",
+        )?;
+        let names: Vec<Option<&str>> = reducer
+            .input_gates()
+            .into_iter()
+            .map(|id| -> Option<&str> { reducer.gate(id).unwrap().label() })
+            .collect();
+        assert_eq!(names, Vec::from([Some("x"), Some("y"), Some("z")]));
+        Ok(())
+    }
+
+    #[test]
+    fn bindings() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse(
+            "let nand = !(a & b);
+            let or = a | b;
+            or & nand",
+        )?;
+        assert_eq!(reducer.input_gates().len(), 2);
+        assert_eq!(
+            reducer.truth_table().unwrap(),
+            TruthTable::from_fn(2, |iv: Vec<bool>| -> bool { iv[0] != iv[1] }).unwrap()
+        );
+        // A binding is built once however often it is referred to and
+        // is labeled by its name.
+        let reducer: BTreeReducer<bool> = parse("let x = a & b; x | !x | (c & x)")?;
+        let x: GateId = reducer.gate_named("x").unwrap();
+        assert_eq!(reducer.parents(x).len(), 3);
+        assert_eq!(
+            reducer.truth_table().unwrap(),
+            TruthTable::from_fn(3, |_: Vec<bool>| -> bool { true }).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (s, line, column, kind) in [
+            ("", 1, 1, ParseErrorKind::UnexpectedEnd),
+            ("a &", 1, 4, ParseErrorKind::UnexpectedEnd),
+            ("(a | b", 1, 7, ParseErrorKind::UnexpectedEnd),
+            (
+                "a b",
+                1,
+                3,
+                ParseErrorKind::UnexpectedToken(String::from("b")),
+            ),
+            (
+                "a )",
+                1,
+                3,
+                ParseErrorKind::UnexpectedToken(String::from(")")),
+            ),
+            (
+                "a &\n& b",
+                2,
+                1,
+                ParseErrorKind::UnexpectedToken(String::from("&")),
+            ),
+            (
+                "a + b",
+                1,
+                3,
+                ParseErrorKind::InvalidValue(String::from("+")),
+            ),
+            (
+                "a & 1b",
+                1,
+                5,
+                ParseErrorKind::InvalidValue(String::from("1b")),
+            ),
+            ("let x = a", 1, 10, ParseErrorKind::UnexpectedEnd),
+            (
+                "let x a; x",
+                1,
+                7,
+                ParseErrorKind::UnexpectedToken(String::from("a")),
+            ),
+            (
+                "let x = a; let x = b; x",
+                1,
+                16,
+                ParseErrorKind::DuplicateName(String::from("x")),
+            ),
+            (
+                "let y = a; let a = b; y",
+                1,
+                16,
+                ParseErrorKind::DuplicateName(String::from("a")),
+            ),
+            (
+                "let x = !x; x",
+                1,
+                10,
+                ParseErrorKind::UnknownName(String::from("x")),
+            ),
+            (
+                "let = a; a",
+                1,
+                5,
+                ParseErrorKind::UnexpectedToken(String::from("=")),
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                parse(s),
+                Err(ParseError::new(*line, *column, kind.clone())),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn print_xor() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series_0: GateId = reducer.add_gate(reducer.root())?;
+        let parallel_1: GateId = reducer.add_gate(series_0)?;
+        let series_1: GateId = reducer.add_gate(series_0)?;
+        let input_0: GateId = reducer.add_gate_with(GateBuilder::new(parallel_1).label("a"))?;
+        let input_1: GateId = reducer.add_gate_with(GateBuilder::new(parallel_1).label("b"))?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+        reducer.reprogram(String::from("010100"))?;
+        reducer.reconfigure(String::from("000100"))?;
+        assert_eq!(print(&reducer), "a ^ b");
+        // Only the inputs are shared.
+        assert_eq!(print_bound(&reducer), "a ^ b");
+
+        // XNOR
+        reducer.reconfigure(String::from("010100"))?;
+        assert_eq!(print(&reducer), "!(a ^ b)");
+        reducer.reconfigure(String::from("000100"))?;
+
+        // Invert the root and share the NAND gate with a third input.
+        let input_2: GateId = reducer.add_gate(reducer.root())?;
+        let parallel_2: GateId = reducer.add_gate(reducer.root())?;
+        reducer.short(parallel_2, series_1)?;
+        reducer.short(parallel_2, input_2)?;
+        reducer.reprogram(String::from("01010000"))?;
+        reducer.reconfigure(String::from("10010000"))?;
+        assert_eq!(
+            print(&reducer),
+            "!((a | b) & !(a & b) | g6 | !(a & b) | g6)"
+        );
+        assert_eq!(
+            print_bound(&reducer),
+            "let g3 = !(a & b);\n!((a | b) & g3 | g6 | g3 | g6)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), ParseError> {
+        for s in [
+            "a",
+            "!a",
+            "a & b",
+            "!(a | b)",
+            "a | b & c",
+            "(a | b) & c",
+            "!(a & b) | c",
+            "a ^ b ^ c",
+            "(a & b) | !c",
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = parse(s)?;
+            for printed in [print(&reducer), print_bound(&reducer)].iter() {
+                let parsed: BTreeReducer<bool> = parse(printed)?;
+                assert_eq!(
+                    parsed.truth_table().unwrap(),
+                    reducer.truth_table().unwrap(),
+                    "{}",
+                    printed
+                );
+            }
+        }
+        assert_eq!(print(&parse("a | b & c")?), "a | b & c");
+        assert_eq!(print(&parse("(a | b) & c")?), "(a | b) & c");
+        assert_eq!(print(&parse("!(a & b) | c")?), "!(a & b) | c");
+        assert_eq!(
+            print_bound(&parse("let x = a & b; x | !x")?),
+            "let x = a & b;\nx | !x"
+        );
+        for s in [
+            "a ^ b",
+            "!(a ^ b)",
+            "a ^ b ^ c",
+            "a ^ (b ^ c)",
+            "a & b ^ c",
+            "(a | b) ^ c",
+            "a ^ b | c",
+        ]
+        .iter()
+        {
+            assert_eq!(print(&parse(s)?), *s);
+            // The operands of an XOR are not bound for being shared by
+            // the gates beneath it.
+            assert_eq!(print_bound(&parse(s)?), *s);
+        }
+        Ok(())
+    }
+}
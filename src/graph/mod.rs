@@ -0,0 +1,94 @@
+use crate::parse::is_name;
+use crate::reducer::{BTreeReducer, GateId};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Orders the gates on which any of `outputs` depends such that every
+/// gate follows all of its children.
+pub(crate) fn postorder(reducer: &BTreeReducer<bool>, outputs: &[GateId]) -> Vec<GateId> {
+    let mut visited: BTreeSet<GateId> = BTreeSet::new();
+    let mut order: Vec<GateId> = Vec::new();
+    // Every gate is pushed once to be expanded and once more, beneath
+    // its children, to be emitted.
+    let mut stack: Vec<(GateId, bool)> = outputs
+        .iter()
+        .rev()
+        .map(|id| -> (GateId, bool) { (*id, false) })
+        .collect();
+    while let Some((id, expanded)) = stack.pop() {
+        if expanded {
+            order.push(id);
+        } else if visited.insert(id) {
+            stack.push((id, true));
+            for child in reducer.children(id).into_iter().flatten().rev() {
+                if !visited.contains(child) {
+                    stack.push((*child, false));
+                }
+            }
+        }
+    }
+    order
+}
+
+/// Orders the gates reachable from the root such that every gate
+/// follows all of its parents, breaking ties by `GateId`.
+pub(crate) fn topological_order(reducer: &BTreeReducer<bool>) -> Vec<GateId> {
+    let mut reachable: BTreeSet<GateId> = BTreeSet::new();
+    let mut stack: Vec<GateId> = Vec::new();
+    stack.push(reducer.root());
+    while let Some(id) = stack.pop() {
+        if reachable.insert(id) {
+            stack.extend(reducer.children(id).into_iter().flatten());
+        }
+    }
+    let mut in_degree: BTreeMap<GateId, usize> = reachable
+        .iter()
+        .map(|id| -> (GateId, usize) {
+            (*id, reducer.parents(*id).intersection(&reachable).count())
+        })
+        .collect();
+    let mut ready: BTreeSet<GateId> = BTreeSet::new();
+    ready.insert(reducer.root());
+    let mut order: Vec<GateId> = Vec::new();
+    while let Some(id) = ready.iter().next().cloned() {
+        ready.remove(&id);
+        order.push(id);
+        for child in reducer.children(id).into_iter().flatten() {
+            let d: &mut usize = in_degree.get_mut(child).unwrap();
+            *d -= 1;
+            if *d == 0 {
+                ready.insert(*child);
+            }
+        }
+    }
+    order
+}
+
+/// Assigns every gate a unique name.
+pub(crate) fn names(reducer: &BTreeReducer<bool>) -> BTreeMap<GateId, String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for gate in reducer.gates() {
+        if let Some(label) = gate.label() {
+            if is_name(label) {
+                *counts.entry(label).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut names: BTreeMap<GateId, String> = BTreeMap::new();
+    for gate in reducer.gates() {
+        let name: String = match gate.label() {
+            Some(label) if counts.get(label) == Some(&1) => String::from(label),
+            _ => {
+                let mut name: String = format!("g{}", gate.id());
+                while counts.contains_key(name.as_str()) {
+                    name.push('_');
+                }
+                name
+            }
+        };
+        names.insert(gate.id(), name);
+    }
+    names
+}
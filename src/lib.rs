@@ -4,4 +4,33 @@ extern crate alloc;
 /// `Error` type is re-exported from the separate btree_error crate.
 pub type Error = btree_error::Error;
 
+pub use crate::bdd::Bdd;
+pub use crate::cnf::Cnf;
+pub use crate::equivalence::{equivalent, Counterexample};
+pub use crate::minimize::Minimization;
+pub use crate::parse::{ParseError, ParseErrorKind};
+pub use crate::reducer::api::{
+    AddGate, AddOutput, Configuration, Dimension, Input, Output, Program, Reconfigure, Reinput,
+    RemoveGate, RemoveShort, Reprogram, Short, Transition,
+};
+pub use crate::reducer::{
+    bool_to_str, try_str_to_bool, BTreeReducer, Gate, GateBuilder, GateId, Word,
+};
+pub use crate::truth_table::TruthTable;
+
+pub mod aiger;
+mod bdd;
+pub mod blif;
+mod cnf;
+pub mod dot;
+mod equivalence;
+pub mod expression;
+mod graph;
+mod minimize;
+pub mod netlist;
+mod parse;
+pub mod prelude;
 mod reducer;
+mod solver;
+mod truth_table;
+pub mod verilog;
@@ -0,0 +1,206 @@
+use crate::reducer::{BTreeReducer, Gate, GateId};
+use crate::truth_table::{sum_of_products, Cube, TruthTable};
+use crate::Error;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::iter;
+
+mod test;
+
+/// `Implicant` is a product term over the rows of a truth table; it
+/// covers every row which equals `value` in each bit not set in `mask`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+struct Implicant {
+    value: usize,
+    mask: usize,
+}
+
+impl Implicant {
+    fn covers(&self, row: usize) -> bool {
+        row & !self.mask == self.value
+    }
+
+    fn cube(&self, inputs: usize) -> Cube {
+        (0..inputs)
+            .map(|j| -> Option<bool> {
+                if (self.mask >> j) & 1 == 1 {
+                    None
+                } else {
+                    Some((self.value >> j) & 1 == 1)
+                }
+            })
+            .collect()
+    }
+}
+
+/// `Minimization` reports the result of `BTreeReducer::minimize`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Minimization {
+    /// The minimized reducer.
+    pub reducer: BTreeReducer<bool>,
+    /// The number of gates of the original reducer.
+    pub gates_before: usize,
+    /// The number of gates of the minimized reducer.
+    pub gates_after: usize,
+}
+
+impl Minimization {
+    /// The number of gates removed by the minimization.
+    pub fn gates_saved(&self) -> usize {
+        self.gates_before - self.gates_after
+    }
+}
+
+impl BTreeReducer<bool> {
+    /// Rebuilds the reducer as a minimal sum of products of its inputs;
+    /// see `minimize_with`.
+    pub fn minimize(&self) -> Result<Minimization, Error> {
+        let inputs: usize = self.input_gates().len();
+        self.minimize_with(&TruthTable::from_fn(inputs, |_| -> bool { false })?)
+    }
+
+    /// Rebuilds the reducer as a minimal sum of products of its inputs,
+    /// where the output of every row set in `dont_cares` may be either
+    /// zero (0) or one (1). The prime implicants are found by the method
+    /// of Quine and McCluskey and the rows are covered by the essential
+    /// prime implicants followed by those which cover the most remaining
+    /// rows. The complement of the function is minimized likewise and
+    /// whichever needs fewer gates is kept. If neither needs fewer gates
+    /// than the reducer itself, the reducer is returned unchanged.
+    ///
+    /// The root and the input gates keep their labels, and the input
+    /// gates their order. Only the root is minimized, so a reducer with
+    /// any other output gate is rejected, as is one of more than
+    /// `TruthTable::MAX_INPUTS` inputs.
+    pub fn minimize_with(&self, dont_cares: &TruthTable) -> Result<Minimization, Error> {
+        if self.outputs().len() > 1 {
+            return Err(Error::ImproperDimension);
+        }
+        let table: TruthTable = self.truth_table()?;
+        if dont_cares.inputs() != table.inputs() {
+            return Err(Error::ImproperDimension);
+        }
+        let inputs: usize = table.inputs();
+        let gates_before: usize = self.gates().len();
+        let dc: Vec<usize> = (0..table.rows())
+            .filter(|row| -> bool { dont_cares.get(*row) == Some(true) })
+            .collect();
+        let mut best: BTreeReducer<bool> = self.clone();
+        for invert in [false, true].iter() {
+            let on: Vec<usize> = (0..table.rows())
+                .filter(|row| -> bool {
+                    dont_cares.get(*row) == Some(false) && table.get(*row) != Some(*invert)
+                })
+                .collect();
+            let cubes: Vec<Cube> = cover(&on, &prime_implicants(&on, &dc))
+                .iter()
+                .map(|i| -> Cube { i.cube(inputs) })
+                .collect();
+            let mut reducer: BTreeReducer<bool> = sum_of_products(inputs, &cubes, *invert);
+            if reducer.gates().len() < best.gates().len() {
+                let roots: (GateId, GateId) = (self.root(), reducer.root());
+                let inputs = self.input_gates().into_iter().zip(reducer.input_gates());
+                for (from, to) in iter::once(roots).chain(inputs) {
+                    if let Some(label) = self.gate(from).unwrap().label() {
+                        let mut gate: Gate<bool> = reducer.gate(to).unwrap().clone();
+                        gate.relabel(label);
+                        reducer.update(to, gate)?;
+                    }
+                }
+                best = reducer;
+            }
+        }
+        let gates_after: usize = best.gates().len();
+        Ok(Minimization {
+            reducer: best,
+            gates_before,
+            gates_after,
+        })
+    }
+}
+
+/// Merges implicants which differ in a single bit until no more can be
+/// merged; those which were never merged are prime.
+fn prime_implicants(on: &[usize], dc: &[usize]) -> Vec<Implicant> {
+    let mut primes: Vec<Implicant> = Vec::new();
+    let mut current: BTreeSet<Implicant> = on
+        .iter()
+        .chain(dc.iter())
+        .map(|row| -> Implicant {
+            Implicant {
+                value: *row,
+                mask: 0,
+            }
+        })
+        .collect();
+    while !current.is_empty() {
+        // Only implicants with the same mask can be merged.
+        let mut by_mask: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for i in current.iter() {
+            by_mask.entry(i.mask).or_default().push(i.value);
+        }
+        let mut merged: BTreeSet<Implicant> = BTreeSet::new();
+        let mut next: BTreeSet<Implicant> = BTreeSet::new();
+        for (mask, values) in by_mask.iter() {
+            for (k, x) in values.iter().enumerate() {
+                for y in values[k + 1..].iter() {
+                    let difference: usize = x ^ y;
+                    if difference.count_ones() == 1 {
+                        merged.insert(Implicant {
+                            value: *x,
+                            mask: *mask,
+                        });
+                        merged.insert(Implicant {
+                            value: *y,
+                            mask: *mask,
+                        });
+                        next.insert(Implicant {
+                            value: x & !difference,
+                            mask: mask | difference,
+                        });
+                    }
+                }
+            }
+        }
+        primes.extend(current.difference(&merged));
+        current = next;
+    }
+    primes
+}
+
+/// Chooses the prime implicants which cover every row of `on`.
+fn cover(on: &[usize], primes: &[Implicant]) -> Vec<Implicant> {
+    let mut uncovered: BTreeSet<usize> = on.iter().cloned().collect();
+    let mut chosen: Vec<Implicant> = Vec::new();
+    // A prime implicant is essential if it is the only one to cover
+    // some row.
+    for row in on.iter() {
+        let covering: Vec<&Implicant> = primes
+            .iter()
+            .filter(|p| -> bool { p.covers(*row) })
+            .collect();
+        if covering.len() == 1 && !chosen.contains(covering[0]) {
+            chosen.push(*covering[0]);
+        }
+    }
+    for p in chosen.iter() {
+        uncovered.retain(|row| -> bool { !p.covers(*row) });
+    }
+    while !uncovered.is_empty() {
+        // Prefer the implicant with the fewest literals between those
+        // which cover as many rows.
+        let p: Implicant = *primes
+            .iter()
+            .max_by_key(|p| -> (usize, u32) {
+                let rows: usize = uncovered
+                    .iter()
+                    .filter(|row| -> bool { p.covers(**row) })
+                    .count();
+                (rows, p.mask.count_ones())
+            })
+            .unwrap();
+        uncovered.retain(|row| -> bool { !p.covers(*row) });
+        chosen.push(p);
+    }
+    chosen
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::expression::parse;
+    use crate::minimize::Minimization;
+    use crate::parse::ParseError;
+    use crate::reducer::api::AddOutput;
+    use crate::reducer::{BTreeReducer, Gate};
+    use crate::truth_table::TruthTable;
+    use crate::Error;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn minimize() -> Result<(), ParseError> {
+        // (a & b) | (a & !b) | (a & c) = a
+        let reducer: BTreeReducer<bool> = parse("(a & b) | (a & !b) | (a & b & c)")?;
+        let minimization: Minimization = reducer.minimize().unwrap();
+        assert_eq!(
+            minimization.reducer.truth_table().unwrap(),
+            reducer.truth_table().unwrap()
+        );
+        assert_eq!(minimization.gates_before, reducer.gates().len());
+        assert_eq!(minimization.gates_after, minimization.reducer.gates().len());
+        assert!(minimization.gates_saved() > 0);
+        // The inputs keep their order and labels.
+        let labels: Vec<Option<&str>> = minimization
+            .reducer
+            .input_gates()
+            .into_iter()
+            .map(|id| -> Option<&str> { minimization.reducer.gate(id).unwrap().label() })
+            .collect();
+        assert_eq!(labels, Vec::from([Some("a"), Some("b"), Some("c")]));
+        Ok(())
+    }
+
+    #[test]
+    fn labels() -> Result<(), ParseError> {
+        let mut reducer: BTreeReducer<bool> = parse("(a & b) | (a & !b)")?;
+        let mut root: Gate<bool> = reducer.gate(reducer.root()).unwrap().clone();
+        root.relabel("y");
+        reducer.update(reducer.root(), root).unwrap();
+        let minimization: Minimization = reducer.minimize().unwrap();
+        assert!(minimization.gates_saved() > 0);
+        let minimized: &BTreeReducer<bool> = &minimization.reducer;
+        assert_eq!(minimized.gate(minimized.root()).unwrap().label(), Some("y"));
+        Ok(())
+    }
+
+    #[test]
+    fn outputs() -> Result<(), ParseError> {
+        // Only the root would be minimized, dropping the second output.
+        let mut reducer: BTreeReducer<bool> = parse("let x = a & b; x | (a & !b)")?;
+        let x = reducer.gate_named("x").unwrap();
+        reducer.add_output(x).unwrap();
+        assert_eq!(reducer.minimize(), Err(Error::ImproperDimension));
+        let dont_cares: TruthTable = TruthTable::from_fn(2, |_| -> bool { false }).unwrap();
+        assert_eq!(
+            reducer.minimize_with(&dont_cares),
+            Err(Error::ImproperDimension)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn already_minimal() -> Result<(), ParseError> {
+        // The XOR gate needs fewer gates than its sum of products.
+        let reducer: BTreeReducer<bool> = parse("a ^ b")?;
+        let minimization: Minimization = reducer.minimize().unwrap();
+        assert_eq!(minimization.reducer, reducer);
+        assert_eq!(minimization.gates_saved(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn complement() -> Result<(), ParseError> {
+        // Every row but one is one (1), so the complement is smaller.
+        let reducer: BTreeReducer<bool> =
+            parse("(a | b | c) & (!a | b | c) & (a | !b | c) & (!a | !b | c) & (a | b | !c)")?;
+        let minimization: Minimization = reducer.minimize().unwrap();
+        assert_eq!(
+            minimization.reducer.truth_table().unwrap(),
+            reducer.truth_table().unwrap()
+        );
+        assert!(minimization.gates_saved() > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn dont_cares() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse("a & b & !c | a & !b & c")?;
+        // Neither b & c nor !b & !c ever occur.
+        let dont_cares: TruthTable =
+            TruthTable::from_fn(3, |iv: Vec<bool>| -> bool { iv[1] == iv[2] }).unwrap();
+        let minimization: Minimization = reducer.minimize_with(&dont_cares).unwrap();
+        let table: TruthTable = reducer.truth_table().unwrap();
+        let minimized: TruthTable = minimization.reducer.truth_table().unwrap();
+        for row in 0..table.rows() {
+            if dont_cares.get(row) == Some(false) {
+                assert_eq!(minimized.get(row), table.get(row));
+            }
+        }
+        // Only a remains.
+        assert_eq!(
+            minimized,
+            TruthTable::from_fn(3, |iv: Vec<bool>| -> bool { iv[0] }).unwrap()
+        );
+        assert!(minimization.gates_saved() > reducer.minimize().unwrap().gates_saved());
+        assert!(reducer
+            .minimize_with(&TruthTable::from_fn(2, |_| -> bool { false }).unwrap())
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn exhaustive() {
+        // Every function of three (3) inputs is preserved.
+        for f in 0..256usize {
+            let bits: Vec<bool> = (0..8).map(|row| -> bool { (f >> row) & 1 == 1 }).collect();
+            let table: TruthTable = TruthTable::from_bits(3, &bits).unwrap();
+            let reducer: BTreeReducer<bool> = BTreeReducer::from_truth_table(&table).unwrap();
+            let minimization: Minimization = reducer.minimize().unwrap();
+            assert_eq!(minimization.reducer.truth_table().unwrap(), table);
+            assert!(minimization.gates_after <= minimization.gates_before);
+        }
+    }
+}
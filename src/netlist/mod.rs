@@ -0,0 +1,336 @@
+//! A human readable, line oriented netlist format for `BTreeReducer<bool>`.
+//!
+//! ```text
+//! # XOR
+//! root and program=1
+//! gate or and
+//! gate nand and program=1 configuration=1
+//! gate a or input=1
+//! gate b or
+//! short nand a
+//! short nand b
+//! ```
+//!
+//! The first statement names the `root` gate, every `gate` statement
+//! names a new gate followed by its parent, if it has one, and every
+//! `short` statement adds an edge from the first named gate to the
+//! second. Every `output` statement promotes the named gate to an output
+//! of the reducer. The `program`, `configuration` and `input` bits
+//! default to zero (0) and everything following a `#` is a comment.
+
+use crate::graph::names;
+use crate::parse::{is_name, tokenize, try_parse_bit, ParseError, ParseErrorKind, Token};
+use crate::reducer::api::{
+    AddOutput, Configuration, Input, Output, Program, Reconfigure, Reinput, RemoveShort, Reprogram,
+    Short,
+};
+use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub(crate) mod test;
+
+/// `Bits` holds the optional `key=bit` attributes of a statement.
+#[derive(Default)]
+struct Bits {
+    program: Option<bool>,
+    configuration: Option<bool>,
+    input: Option<bool>,
+}
+
+/// Parses a netlist into a `BTreeReducer<bool>`. The reducer is
+/// resolved once every statement has been applied.
+pub fn parse(s: &str) -> Result<BTreeReducer<bool>, ParseError> {
+    let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+    let mut names: BTreeMap<&str, GateId> = BTreeMap::new();
+    let mut shorts: Vec<(usize, Token<'_>, Token<'_>)> = Vec::new();
+    let mut outputs: Vec<(usize, Token<'_>)> = Vec::new();
+    let mut root: bool = false;
+    let mut lines: usize = 0;
+    for (n, line) in s.lines().enumerate() {
+        let line_number: usize = n + 1;
+        lines = line_number;
+        let tokens: Vec<Token<'_>> = tokenize(line, '#');
+        let keyword: &Token<'_> = match tokens.first() {
+            Some(t) => t,
+            None => continue,
+        };
+        // The root must be named exactly once, before any other statement.
+        if root == (keyword.text == "root") {
+            return Err(ParseError::new(
+                line_number,
+                keyword.column,
+                ParseErrorKind::UnexpectedToken(String::from(keyword.text)),
+            ));
+        }
+        match keyword.text {
+            "root" => {
+                let name: Token<'_> = expect_name(line_number, line, &tokens, 1)?;
+                let bits: Bits = parse_bits(line_number, &tokens[2..])?;
+                let mut gate: Gate<bool> = reducer.gate(reducer.root()).unwrap().clone();
+                gate.relabel(name.text);
+                gate.reprogram(bits.program.unwrap_or_default()).unwrap();
+                gate.reconfigure(bits.configuration.unwrap_or_default())
+                    .unwrap();
+                gate.reinput(bits.input.unwrap_or_default()).unwrap();
+                reducer.update(reducer.root(), gate).unwrap();
+                names.insert(name.text, reducer.root());
+                root = true;
+            }
+            "gate" => {
+                let name: Token<'_> = expect_name(line_number, line, &tokens, 1)?;
+                if names.contains_key(name.text) {
+                    return Err(ParseError::new(
+                        line_number,
+                        name.column,
+                        ParseErrorKind::DuplicateName(String::from(name.text)),
+                    ));
+                }
+                // A gate without a parent is added beneath the root and
+                // detached at once.
+                let parent: Option<GateId> = match tokens.get(2) {
+                    Some(t) if !t.text.contains('=') => Some(resolve(
+                        line_number,
+                        &names,
+                        expect_name(line_number, line, &tokens, 2)?,
+                    )?),
+                    _ => None,
+                };
+                let bits: Bits = parse_bits(line_number, &tokens[2 + parent.is_some() as usize..])?;
+                let id: GateId = reducer
+                    .add_gate_with(
+                        GateBuilder::new(parent.unwrap_or(reducer.root()))
+                            .program(bits.program.unwrap_or_default())
+                            .configuration(bits.configuration.unwrap_or_default())
+                            .input(bits.input.unwrap_or_default())
+                            .label(name.text),
+                    )
+                    .unwrap();
+                if parent.is_none() {
+                    reducer.remove_short(reducer.root(), id).unwrap();
+                }
+                names.insert(name.text, id);
+            }
+            "short" => {
+                let x: Token<'_> = expect_name(line_number, line, &tokens, 1)?;
+                let y: Token<'_> = expect_name(line_number, line, &tokens, 2)?;
+                if let Some(t) = tokens.get(3) {
+                    return Err(ParseError::new(
+                        line_number,
+                        t.column,
+                        ParseErrorKind::UnexpectedToken(String::from(t.text)),
+                    ));
+                }
+                // Shorts are applied once every gate has been defined,
+                // so they may refer to gates defined further down.
+                shorts.push((line_number, x, y));
+            }
+            "output" => {
+                let x: Token<'_> = expect_name(line_number, line, &tokens, 1)?;
+                if let Some(t) = tokens.get(2) {
+                    return Err(ParseError::new(
+                        line_number,
+                        t.column,
+                        ParseErrorKind::UnexpectedToken(String::from(t.text)),
+                    ));
+                }
+                outputs.push((line_number, x));
+            }
+            _ => {
+                return Err(ParseError::new(
+                    line_number,
+                    keyword.column,
+                    ParseErrorKind::UnexpectedToken(String::from(keyword.text)),
+                ))
+            }
+        }
+    }
+    if !root {
+        return Err(ParseError::new(lines + 1, 1, ParseErrorKind::UnexpectedEnd));
+    }
+    for (line_number, x, y) in shorts {
+        let x_id: GateId = resolve(line_number, &names, x)?;
+        let y_id: GateId = resolve(line_number, &names, y)?;
+        reducer.short(x_id, y_id).map_err(|_| -> ParseError {
+            ParseError::new(line_number, x.column, ParseErrorKind::InvalidEdge)
+        })?;
+    }
+    for (line_number, x) in outputs {
+        let x_id: GateId = resolve(line_number, &names, x)?;
+        reducer.add_output(x_id).unwrap();
+    }
+    let _: bool = reducer.output();
+    Ok(reducer)
+}
+
+/// Prints a `BTreeReducer<bool>` as a netlist which `parse` accepts.
+/// Gates are named by their label, or by their `GateId` if they are
+/// unlabeled or their label is not unique. Every gate is printed, even
+/// one which is unreachable from the root, and the input gates are
+/// printed in order so that `parse` transitions them in the same order.
+pub fn print(reducer: &BTreeReducer<bool>) -> String {
+    let names: BTreeMap<GateId, String> = names(reducer);
+    let order: Vec<GateId> = declaration_order(reducer);
+    let position: BTreeMap<GateId, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(i, id)| -> (GateId, usize) { (*id, i) })
+        .collect();
+    let mut statements: String = String::new();
+    let mut shorts: String = String::new();
+    for id in order.iter() {
+        let gate: &Gate<bool> = reducer.gate(*id).unwrap();
+        let leaf: bool = reducer.children(*id).into_iter().flatten().next().is_none();
+        if *id == reducer.root() {
+            statements.push_str(&format!("root {}", names[id]));
+        } else {
+            let parents: BTreeSet<GateId> = reducer.parents(*id);
+            let parent: Option<&GateId> = parents.iter().min_by_key(|p| -> usize { position[p] });
+            match parent {
+                Some(p) => statements.push_str(&format!("gate {} {}", names[id], names[p])),
+                None => statements.push_str(&format!("gate {}", names[id])),
+            }
+            for p in parents.iter() {
+                if Some(p) != parent {
+                    shorts.push_str(&format!("short {} {}\n", names[p], names[id]));
+                }
+            }
+        }
+        if gate.program() {
+            statements.push_str(" program=1");
+        }
+        if gate.configuration() {
+            statements.push_str(" configuration=1");
+        }
+        if leaf && gate.input() {
+            statements.push_str(" input=1");
+        }
+        statements.push('\n');
+    }
+    statements.push_str(&shorts);
+    for id in reducer.outputs().iter().skip(1) {
+        statements.push_str(&format!("output {}\n", names[id]));
+    }
+    statements
+}
+
+/// Orders every gate such that each follows all of its parents, and
+/// every input gate the previous one, breaking ties by `GateId`.
+fn declaration_order(reducer: &BTreeReducer<bool>) -> Vec<GateId> {
+    let inputs: Vec<GateId> = reducer.input_gates();
+    let next: BTreeMap<GateId, GateId> = inputs
+        .windows(2)
+        .map(|w| -> (GateId, GateId) { (w[0], w[1]) })
+        .collect();
+    let mut in_degree: BTreeMap<GateId, usize> = reducer
+        .gates()
+        .iter()
+        .map(|gate| -> (GateId, usize) { (gate.id(), reducer.parents(gate.id()).len()) })
+        .collect();
+    for follower in next.values() {
+        *in_degree.get_mut(follower).unwrap() += 1;
+    }
+    let mut ready: BTreeSet<GateId> = in_degree
+        .iter()
+        .filter(|(_, d)| -> bool { **d == 0 })
+        .map(|(id, _)| -> GateId { *id })
+        .collect();
+    let mut order: Vec<GateId> = Vec::new();
+    while let Some(id) = ready.iter().next().cloned() {
+        ready.remove(&id);
+        order.push(id);
+        for follower in reducer
+            .children(id)
+            .into_iter()
+            .flatten()
+            .chain(next.get(&id))
+        {
+            let d: &mut usize = in_degree.get_mut(follower).unwrap();
+            *d -= 1;
+            if *d == 0 {
+                ready.insert(*follower);
+            }
+        }
+    }
+    order
+}
+
+fn expect_name<'a>(
+    line_number: usize,
+    line: &str,
+    tokens: &[Token<'a>],
+    i: usize,
+) -> Result<Token<'a>, ParseError> {
+    match tokens.get(i) {
+        Some(t) if is_name(t.text) => Ok(*t),
+        Some(t) => Err(ParseError::new(
+            line_number,
+            t.column,
+            ParseErrorKind::InvalidValue(String::from(t.text)),
+        )),
+        None => Err(ParseError::new(
+            line_number,
+            line.chars().count() + 1,
+            ParseErrorKind::UnexpectedEnd,
+        )),
+    }
+}
+
+fn resolve(
+    line_number: usize,
+    names: &BTreeMap<&str, GateId>,
+    t: Token<'_>,
+) -> Result<GateId, ParseError> {
+    names.get(t.text).cloned().ok_or_else(|| -> ParseError {
+        ParseError::new(
+            line_number,
+            t.column,
+            ParseErrorKind::UnknownName(String::from(t.text)),
+        )
+    })
+}
+
+fn parse_bits(line_number: usize, tokens: &[Token<'_>]) -> Result<Bits, ParseError> {
+    let mut bits: Bits = Bits::default();
+    for t in tokens {
+        let (key, value): (&str, &str) = match t.text.find('=') {
+            Some(i) => (&t.text[..i], &t.text[i + 1..]),
+            None => {
+                return Err(ParseError::new(
+                    line_number,
+                    t.column,
+                    ParseErrorKind::UnexpectedToken(String::from(t.text)),
+                ))
+            }
+        };
+        let bit: &mut Option<bool> = match key {
+            "program" => &mut bits.program,
+            "configuration" => &mut bits.configuration,
+            "input" => &mut bits.input,
+            _ => {
+                return Err(ParseError::new(
+                    line_number,
+                    t.column,
+                    ParseErrorKind::UnexpectedToken(String::from(key)),
+                ))
+            }
+        };
+        if bit.is_some() {
+            return Err(ParseError::new(
+                line_number,
+                t.column,
+                ParseErrorKind::DuplicateName(String::from(key)),
+            ));
+        }
+        *bit = Some(try_parse_bit(value).ok_or_else(|| -> ParseError {
+            ParseError::new(
+                line_number,
+                t.column + key.chars().count() + 1,
+                ParseErrorKind::InvalidValue(String::from(value)),
+            )
+        })?);
+    }
+    Ok(bits)
+}
@@ -0,0 +1,235 @@
+/// The XOR gate of the module documentation, shared by the tests of
+/// every format.
+#[cfg(test)]
+pub(crate) const XOR: &str = "
+    # XOR
+    root and program=1
+    gate or and
+    gate nand and program=1 configuration=1
+    gate a or input=1
+    gate b or
+    short nand a
+    short nand b
+";
+
+#[cfg(test)]
+mod unit_tests {
+    use crate::netlist::test::XOR;
+    use crate::netlist::{parse, print};
+    use crate::parse::{ParseError, ParseErrorKind};
+    use crate::reducer::api::{
+        AddGate, AddOutput, Configuration, Input, Output, Program, Reconfigure, Reinput,
+        RemoveShort, Reprogram, Short,
+    };
+    use crate::reducer::{BTreeReducer, GateId};
+    use crate::Error;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn parse_xor() -> Result<(), ParseError> {
+        let mut reducer: BTreeReducer<bool> = parse(XOR)?;
+
+        let program: String = reducer.program();
+        assert_eq!(program.as_str(), "10100");
+
+        let configuration: String = reducer.configuration();
+        assert_eq!(configuration.as_str(), "00100");
+
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), "10");
+
+        assert_eq!(reducer.gate(GateId(3)).unwrap().label(), Some("a"));
+        assert_eq!(reducer.children(GateId(2)).unwrap().len(), 2);
+
+        for (is, os) in [("00", "0"), ("10", "1"), ("01", "1"), ("11", "0")].iter() {
+            reducer.reinput(String::from(*is)).unwrap();
+            let output: String = reducer.output();
+            assert_eq!(output.as_str(), *os);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn print_xor() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+        reducer.reprogram(String::from("010100"))?;
+        reducer.reconfigure(String::from("000100"))?;
+        reducer.reinput(String::from("01"))?;
+
+        let netlist: String = print(&reducer);
+        assert_eq!(
+            netlist.as_str(),
+            "root g0\n\
+             gate g1 g0 program=1\n\
+             gate g2 g1\n\
+             gate g3 g1 program=1 configuration=1\n\
+             gate g4 g2\n\
+             gate g5 g2 input=1\n\
+             short g3 g4\n\
+             short g3 g5\n"
+        );
+
+        let mut parsed: BTreeReducer<bool> = parse(&netlist).unwrap();
+        let program: String = parsed.program();
+        assert_eq!(program.as_str(), "010100");
+        let configuration: String = parsed.configuration();
+        assert_eq!(configuration.as_str(), "000100");
+        let input: String = parsed.input();
+        assert_eq!(input.as_str(), "01");
+        let output: bool = parsed.output();
+        assert!(output);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse(XOR)?;
+        let netlist: String = print(&reducer);
+        assert_eq!(parse(&netlist)?, reducer);
+        assert_eq!(print(&parse(&netlist)?), netlist);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_inputs() -> Result<(), ParseError> {
+        // The leaf `a` is shorted to `n`, which follows the leaf `b`,
+        // yet `a` remains the first input once printed.
+        let reducer: BTreeReducer<bool> = parse(
+            "root r program=1\n\
+             gate a r\n\
+             gate m r\n\
+             gate b m configuration=1\n\
+             gate n m program=1 configuration=1\n\
+             short n a\n",
+        )?;
+        assert_eq!(reducer.input_gates(), Vec::from([GateId(1), GateId(3)]));
+        let parsed: BTreeReducer<bool> = parse(&print(&reducer))?;
+        assert_eq!(
+            parsed.truth_table().unwrap(),
+            reducer.truth_table().unwrap()
+        );
+        assert_eq!(
+            parsed.gate(parsed.input_gates()[0]).unwrap().label(),
+            Some("a")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_unreachable() -> Result<(), ParseError> {
+        // A gate without parents is printed, along with its children,
+        // rather than dropped.
+        let mut reducer: BTreeReducer<bool> = parse(XOR)?;
+        reducer.remove_short(reducer.root(), GateId(1)).unwrap();
+        reducer.add_output(GateId(1)).unwrap();
+        let netlist: String = print(&reducer);
+        assert!(netlist.contains("gate or\n"), "{}", netlist);
+        let _: Vec<bool> = reducer.output();
+        assert_eq!(parse(&netlist)?, reducer);
+        assert_eq!(print(&parse(&netlist)?), netlist);
+        Ok(())
+    }
+
+    #[test]
+    fn outputs() -> Result<(), ParseError> {
+        let netlist: &str = "root adder\n\
+                             gate sum adder program=1\n\
+                             gate or sum\n\
+                             gate nand sum program=1 configuration=1\n\
+                             gate a or\n\
+                             gate b or\n\
+                             gate carry adder program=1\n\
+                             short nand a\n\
+                             short nand b\n\
+                             short carry a\n\
+                             short carry b\n\
+                             output sum\n\
+                             output carry\n";
+        let mut reducer: BTreeReducer<bool> = parse(netlist)?;
+        assert_eq!(
+            reducer.outputs(),
+            Vec::from([reducer.root(), GateId(1), GateId(6)])
+        );
+        reducer.reinput(String::from("11")).unwrap();
+        let output: Vec<bool> = reducer.output();
+        assert!(!output[1]);
+        assert!(output[2]);
+
+        let printed: String = print(&reducer);
+        assert!(printed.ends_with("output sum\noutput carry\n"));
+        let mut parsed: BTreeReducer<bool> = parse(&printed)?;
+        let parsed_output: Vec<bool> = parsed.output();
+        assert_eq!(parsed_output, output);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |s: &str| -> (usize, usize, ParseErrorKind) {
+            let e: ParseError = parse(s).unwrap_err();
+            (e.line, e.column, e.kind)
+        };
+        assert_eq!(error(""), (1, 1, ParseErrorKind::UnexpectedEnd));
+        assert_eq!(
+            error("gate a b"),
+            (1, 1, ParseErrorKind::UnexpectedToken(String::from("gate")))
+        );
+        assert_eq!(
+            error("root r\nroot s"),
+            (2, 1, ParseErrorKind::UnexpectedToken(String::from("root")))
+        );
+        assert_eq!(error("root"), (1, 5, ParseErrorKind::UnexpectedEnd));
+        assert_eq!(
+            error("root 0r"),
+            (1, 6, ParseErrorKind::InvalidValue(String::from("0r")))
+        );
+        assert_eq!(
+            error("root r\n  gate a b"),
+            (2, 10, ParseErrorKind::UnknownName(String::from("b")))
+        );
+        assert_eq!(
+            error("root r\ngate r r"),
+            (2, 6, ParseErrorKind::DuplicateName(String::from("r")))
+        );
+        assert_eq!(
+            error("root r program=2"),
+            (1, 16, ParseErrorKind::InvalidValue(String::from("2")))
+        );
+        assert_eq!(
+            error("root r state=1"),
+            (1, 8, ParseErrorKind::UnexpectedToken(String::from("state")))
+        );
+        assert_eq!(
+            error("root r program=1 program=0"),
+            (
+                1,
+                18,
+                ParseErrorKind::DuplicateName(String::from("program"))
+            )
+        );
+        assert_eq!(
+            error("root r\ngate a r\nshort a r"),
+            (3, 7, ParseErrorKind::InvalidEdge)
+        );
+        assert_eq!(
+            error("root r\nshort r a # a is never defined"),
+            (2, 9, ParseErrorKind::UnknownName(String::from("a")))
+        );
+        assert_eq!(
+            error("root r\noutput s"),
+            (2, 8, ParseErrorKind::UnknownName(String::from("s")))
+        );
+        assert_eq!(
+            error("root r\nwire r"),
+            (2, 1, ParseErrorKind::UnexpectedToken(String::from("wire")))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use crate::reducer::api::{Output, Program, RemoveGate, RemoveShort, Reprogram, Short};
+use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// `ParseErrorKind` describes why a textual representation of a
+/// `BTreeReducer` could not be parsed.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseErrorKind {
+    /// A token was found where a different one was expected.
+    UnexpectedToken(String),
+    /// The line or input ended while more tokens were expected.
+    UnexpectedEnd,
+    /// A value was not a valid bit, number or name.
+    InvalidValue(String),
+    /// A name was referenced before it was defined.
+    UnknownName(String),
+    /// A name was defined more than once.
+    DuplicateName(String),
+    /// An edge could not be added to the DAG, i.e. it would have
+    /// introduced a cycle.
+    InvalidEdge,
+}
+
+/// `ParseError` locates a `ParseErrorKind` by its one (1) indexed
+/// line and column in the parsed text.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        ParseError { line, column, kind }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedToken(t) => write!(f, "unexpected token `{}`", t),
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::InvalidValue(v) => write!(f, "invalid value `{}`", v),
+            ParseErrorKind::UnknownName(n) => write!(f, "unknown name `{}`", n),
+            ParseErrorKind::DuplicateName(n) => write!(f, "duplicate name `{}`", n),
+            ParseErrorKind::InvalidEdge => write!(f, "edge would introduce a cycle"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+/// The `ParseErrorKind::UnexpectedToken` of `text` at `line` and `column`.
+pub(crate) fn unexpected(line: usize, column: usize, text: &str) -> ParseError {
+    ParseError::new(
+        line,
+        column,
+        ParseErrorKind::UnexpectedToken(String::from(text)),
+    )
+}
+
+/// The `ParseErrorKind::InvalidValue` of `text` at `line` and `column`.
+pub(crate) fn invalid(line: usize, column: usize, text: &str) -> ParseError {
+    ParseError::new(
+        line,
+        column,
+        ParseErrorKind::InvalidValue(String::from(text)),
+    )
+}
+
+/// The `ParseErrorKind::DuplicateName` of `text` at `line` and `column`.
+pub(crate) fn duplicate(line: usize, column: usize, text: &str) -> ParseError {
+    ParseError::new(
+        line,
+        column,
+        ParseErrorKind::DuplicateName(String::from(text)),
+    )
+}
+
+/// `Token` is a whitespace delimited word along with its one (1)
+/// indexed column.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) struct Token<'a> {
+    pub(crate) column: usize,
+    pub(crate) text: &'a str,
+}
+
+/// Splits a line into whitespace delimited tokens, dropping anything
+/// following the `comment` character.
+pub(crate) fn tokenize(line: &str, comment: char) -> Vec<Token<'_>> {
+    let line: &str = match line.find(comment) {
+        Some(i) => &line[..i],
+        None => line,
+    };
+    let mut tokens: Vec<Token<'_>> = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in line.char_indices().chain(Some((line.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                tokens.push(Token {
+                    column: line[..s].chars().count() + 1,
+                    text: &line[s..i],
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Returns true if `s` is a valid identifier, i.e. an ASCII letter
+/// or underscore followed by any number of ASCII alphanumerics or
+/// underscores.
+pub(crate) fn is_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| -> bool { c.is_ascii_alphanumeric() || c == '_' })
+        }
+        _ => false,
+    }
+}
+
+/// Parses a single `0` or `1` character as a bit.
+pub(crate) fn try_parse_bit(s: &str) -> Option<bool> {
+    match s {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    }
+}
+
+/// Shorts `x` to `y` unless they are already connected.
+pub(crate) fn connect(reducer: &mut BTreeReducer<bool>, x: GateId, y: GateId) {
+    if !reducer.children(x).unwrap().contains(&y) {
+        reducer.short(x, y).unwrap();
+    }
+}
+
+/// `Scaffold` is a `BTreeReducer<bool>` under construction by a parser
+/// of a format whose inputs and outputs need not be connected to each
+/// other. Every input hangs beneath a placeholder gate beneath the root
+/// until `prune` decides whether it is still needed.
+pub(crate) struct Scaffold {
+    pub(crate) reducer: BTreeReducer<bool>,
+    sink: GateId,
+}
+
+impl Scaffold {
+    /// Adds an input gate for every label, in order, beneath the
+    /// placeholder before any other gate so that the input gates are
+    /// ordered as the labels.
+    pub(crate) fn new<'a, I>(labels: I) -> (Self, Vec<GateId>)
+    where
+        I: IntoIterator<Item = Option<&'a str>>,
+    {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let sink: GateId = reducer
+            .add_gate_with(GateBuilder::new(reducer.root()))
+            .unwrap();
+        let inputs: Vec<GateId> = labels
+            .into_iter()
+            .map(|label| -> GateId {
+                let mut gate: GateBuilder<bool> = GateBuilder::new(sink);
+                if let Some(label) = label {
+                    gate = gate.label(label);
+                }
+                reducer.add_gate_with(gate).unwrap()
+            })
+            .collect();
+        (Scaffold { reducer, sink }, inputs)
+    }
+
+    /// The placeholder, beneath which any input or output which is not
+    /// otherwise connected to the root is added.
+    pub(crate) fn sink(&self) -> GateId {
+        self.sink
+    }
+
+    /// Disconnects the placeholder from every gate with another parent.
+    /// The placeholder is kept only if some gate hangs beneath it alone,
+    /// in which case it is returned, programmed opposite to the root, so
+    /// that the caller may add `x` and `!x` beneath it to make its
+    /// output the identity of the root, i.e. `x | !x | ...` beneath a
+    /// series (AND) root or `x & !x & ...` beneath a parallel (OR) root.
+    /// Otherwise it is removed.
+    pub(crate) fn prune(&mut self) -> Option<GateId> {
+        let children: Vec<GateId> = self
+            .reducer
+            .children(self.sink)
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect();
+        let mut needed: bool = false;
+        for child in children.iter() {
+            if self.reducer.parents(*child).len() > 1 {
+                self.reducer.remove_short(self.sink, *child).unwrap();
+            } else {
+                needed = true;
+            }
+        }
+        if !needed {
+            self.reducer.remove_gate(self.sink, false).unwrap();
+            return None;
+        }
+        let program: bool = self.reducer.gate(self.reducer.root()).unwrap().program();
+        let mut gate: Gate<bool> = self.reducer.gate(self.sink).unwrap().clone();
+        gate.reprogram(!program).unwrap();
+        self.reducer.update(self.sink, gate).unwrap();
+        Some(self.sink)
+    }
+
+    /// Resolves and returns the reducer.
+    pub(crate) fn finish(self) -> BTreeReducer<bool> {
+        let mut reducer: BTreeReducer<bool> = self.reducer;
+        let _: bool = reducer.output();
+        reducer
+    }
+}
@@ -0,0 +1,11 @@
+//! The `prelude` re-exports the `BTreeReducer` data structure along
+//! with every trait required to build and transition one, so that a
+//! single `use btree_reducer::prelude::*;` is sufficient.
+
+pub use crate::reducer::api::{
+    AddGate, AddOutput, Configuration, Dimension, Input, Output, Program, Reconfigure, Reinput,
+    RemoveGate, RemoveShort, Reprogram, Short, Transition,
+};
+pub use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId, Word};
+pub use crate::truth_table::TruthTable;
+pub use crate::Error;
@@ -104,5 +104,18 @@ pub trait RemoveShort<T> {
 
 /// `AddGate`
 pub trait AddGate<T> {
-    fn add_gate(&mut self, g: T) -> T;
+    type Error;
+    fn add_gate(&mut self, g: T) -> Result<T, Self::Error>;
+}
+
+/// `AddOutput`
+pub trait AddOutput<T> {
+    type Error;
+    fn add_output(&mut self, g: T) -> Result<(), Self::Error>;
+}
+
+/// `RemoveGate`
+pub trait RemoveGate<T> {
+    type Error;
+    fn remove_gate(&mut self, g: T, descendants: bool) -> Result<BTreeSet<T>, Self::Error>;
 }
@@ -0,0 +1,127 @@
+use crate::reducer::{BTreeReducer, Gate, GateId};
+use crate::Error;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use btree_dag::Connections;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+/// `Word` is an unsigned integer whose bits each hold the state of a
+/// `BTreeReducer<bool>` for a separate input vector. See
+/// `BTreeReducer::evaluate_word`.
+pub trait Word:
+    Copy
+    + Eq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    /// The number of input vectors held by a single word.
+    const BITS: usize;
+    const ZERO: Self;
+
+    /// A word with every bit equal to `b`.
+    fn splat(b: bool) -> Self {
+        if b {
+            !Self::ZERO
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+macro_rules! word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+            }
+        )*
+    };
+}
+
+word!(u8, u16, u32, u64, u128);
+
+impl BTreeReducer<bool> {
+    /// Computes the output of the root for `W::BITS` input vectors in a
+    /// single pass. Bit `k` of the `i`th word of `iv` is the input of the
+    /// `i`th input gate in the `k`th input vector, and bit `k` of the
+    /// result is the output of the root for the `k`th input vector. The
+    /// words are ordered as for `Reinput<Vec<bool>>`.
+    pub fn evaluate_word<W: Word>(&self, iv: &[W]) -> Result<W, Error> {
+        let mut outputs: BTreeMap<GateId, W> = self._input_words(iv)?;
+        Ok(self._evaluate_word(self.root(), &mut outputs))
+    }
+
+    /// Computes the output of every output gate, beginning with the
+    /// root, for `W::BITS` input vectors in a single pass; see
+    /// `evaluate_word`.
+    pub(crate) fn evaluate_outputs_word<W: Word>(&self, iv: &[W]) -> Result<Vec<W>, Error> {
+        let mut outputs: BTreeMap<GateId, W> = self._input_words(iv)?;
+        Ok(self
+            .outputs
+            .iter()
+            .map(|id| -> W { self._evaluate_word(*id, &mut outputs) })
+            .collect())
+    }
+
+    /// Computes the output of the root for every input vector of `ivs`,
+    /// packing them into words of 64 bits and calling `evaluate_word`.
+    pub fn evaluate_many(&self, ivs: &[Vec<bool>]) -> Result<Vec<bool>, Error> {
+        let dimension: usize = self._get_input_contacts().len();
+        let mut outputs: Vec<bool> = Vec::with_capacity(ivs.len());
+        for chunk in ivs.chunks(u64::BITS as usize) {
+            let mut words: Vec<u64> = vec![0; dimension];
+            for (k, iv) in chunk.iter().enumerate() {
+                if iv.len() != dimension {
+                    return Err(Error::ImproperDimension);
+                }
+                for (word, bit) in words.iter_mut().zip(iv.iter()) {
+                    *word |= (*bit as u64) << k;
+                }
+            }
+            let output: u64 = self.evaluate_word(&words)?;
+            outputs.extend((0..chunk.len()).map(|k| -> bool { (output >> k) & 1 == 1 }));
+        }
+        Ok(outputs)
+    }
+
+    /// The output of every input gate for the input words `iv`.
+    fn _input_words<W: Word>(&self, iv: &[W]) -> Result<BTreeMap<GateId, W>, Error> {
+        let inputs: Vec<GateId> = self._get_input_contacts();
+        if inputs.len() != iv.len() {
+            return Err(Error::ImproperDimension);
+        }
+        let mut outputs: BTreeMap<GateId, W> = BTreeMap::new();
+        for (id, word) in inputs.into_iter().zip(iv.iter()) {
+            let gate: &Gate<bool> = &self.gates[&id];
+            outputs.insert(id, *word ^ W::splat(gate.configuration));
+        }
+        Ok(outputs)
+    }
+
+    /// The bitwise counterpart of `_evaluate_branch`.
+    fn _evaluate_word<W: Word>(&self, id: GateId, outputs: &mut BTreeMap<GateId, W>) -> W {
+        if let Some(output) = outputs.get(&id) {
+            return *output;
+        }
+        let gate: &Gate<bool> = &self.gates[&id];
+        let program: W = W::splat(gate.program);
+        // A bit of `state_set` is set once any child differs from the
+        // program for that input vector.
+        let mut state_set: W = W::ZERO;
+        for contact in self.dag.connections(id).into_iter().flatten() {
+            state_set = state_set | (self._evaluate_word(*contact, outputs) ^ program);
+            if state_set == !W::ZERO {
+                break;
+            }
+        }
+        // The input is the program where the state is not set and its
+        // transition, the inverted program, where it is.
+        let output: W = program ^ state_set ^ W::splat(gate.configuration);
+        outputs.insert(id, output);
+        output
+    }
+}
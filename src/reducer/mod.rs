@@ -1,25 +1,115 @@
 use crate::reducer::api::{
-    AddGate, Configuration, Dimension, Input, Output, Program, Reconfigure, Reinput, RemoveShort,
-    Reprogram, Short, Transition,
+    AddGate, AddOutput, Configuration, Dimension, Input, Output, Program, Reconfigure, Reinput,
+    RemoveGate, RemoveShort, Reprogram, Short, Transition,
 };
 use crate::Error;
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
 use btree_dag::{AddEdge, AddVertex, BTreeDAG, Connections, RemoveEdge, RemoveVertex, Vertices};
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-mod api;
+pub use crate::reducer::bitwise::Word;
+
+pub mod api;
+mod bitwise;
+#[cfg(feature = "serde")]
+mod schema;
 mod test;
 
+/// `GateId` is a stable handle to a `Gate` within a `BTreeReducer`.
+/// Unlike the `Gate` itself, the handle does not change when the
+/// gate's input, configuration or program is transitioned.
+#[derive(PartialEq, PartialOrd, Ord, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GateId(pub(crate) usize);
+
+impl fmt::Display for GateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(PartialEq, PartialOrd, Ord, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Gate<T>
 where
     T: Default + Ord + Clone,
 {
-    id: usize,
+    id: GateId,
+    input: T,
+    configuration: T,
+    program: T,
+    label: Option<String>,
+}
+
+impl<T> Gate<T>
+where
+    T: Default + Ord + Clone,
+{
+    pub fn id(&self) -> GateId {
+        self.id
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn relabel(&mut self, l: &str) {
+        self.label = Some(String::from(l));
+    }
+}
+
+/// `GateBuilder` describes a gate to be added beneath `parent` with
+/// an initial input, configuration, program and optional label.
+/// See `BTreeReducer::add_gate_with`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct GateBuilder<T>
+where
+    T: Default + Ord + Clone,
+{
+    parent: GateId,
     input: T,
     configuration: T,
     program: T,
+    label: Option<String>,
+}
+
+impl<T> GateBuilder<T>
+where
+    T: Default + Ord + Clone,
+{
+    pub fn new(parent: GateId) -> Self {
+        GateBuilder {
+            parent,
+            input: T::default(),
+            configuration: T::default(),
+            program: T::default(),
+            label: None,
+        }
+    }
+
+    pub fn input(mut self, i: T) -> Self {
+        self.input = i;
+        self
+    }
+
+    pub fn configuration(mut self, c: T) -> Self {
+        self.configuration = c;
+        self
+    }
+
+    pub fn program(mut self, p: T) -> Self {
+        self.program = p;
+        self
+    }
+
+    pub fn label(mut self, l: &str) -> Self {
+        self.label = Some(String::from(l));
+        self
+    }
 }
 
 impl Transition<bool> for Gate<bool> {
@@ -95,149 +185,635 @@ where
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct BTreeReducer<T>
 where
     T: Default + Ord + Clone,
 {
-    dag: BTreeDAG<Gate<T>>,
+    dag: BTreeDAG<GateId>,
+    // The transpose of `dag`, so that the parents of a gate can be
+    // found without scanning every vertex.
+    parents: BTreeMap<GateId, BTreeSet<GateId>>,
+    gates: BTreeMap<GateId, Gate<T>>,
+    outputs: Vec<GateId>,
+    next: GateId,
+    // Set once the edges have changed without the affected gates being
+    // re-evaluated, after which every gate is resolved on the next call
+    // to `output`. Otherwise the stored state of every gate is current.
+    stale: bool,
 }
 
+// Whether the stored state is stale is not part of the reducer itself.
+impl<T> PartialEq for BTreeReducer<T>
+where
+    T: Default + Ord + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.dag == other.dag
+            && self.gates == other.gates
+            && self.outputs == other.outputs
+            && self.next == other.next
+    }
+}
+
+impl<T> Eq for BTreeReducer<T> where T: Default + Ord + Clone {}
+
 impl<T> BTreeReducer<T>
 where
     T: Default + Ord + Clone,
     Gate<T>: Transition<T>
 {
-    fn new() -> Self {
-        let mut dag: BTreeDAG<Gate<T>> = BTreeDAG::new();
+    pub fn new() -> Self {
+        let mut dag: BTreeDAG<GateId> = BTreeDAG::new();
+        let mut gates: BTreeMap<GateId, Gate<T>> = BTreeMap::new();
         let contact_zero: Gate<T> = Gate {
-            id: usize::default(),
+            id: GateId::default(),
             input: T::default(),
             configuration: T::default(),
             program: T::default(),
+            label: None,
+        };
+        dag.add_vertex(contact_zero.id);
+        let parents: BTreeMap<GateId, BTreeSet<GateId>> =
+            BTreeMap::from([(contact_zero.id, BTreeSet::new())]);
+        gates.insert(contact_zero.id, contact_zero);
+        BTreeReducer {
+            dag,
+            parents,
+            gates,
+            outputs: Vec::from([GateId::default()]),
+            next: GateId(1),
+            stale: false,
+        }
+    }
+
+    pub fn root(&self) -> GateId {
+        GateId::default()
+    }
+
+    /// Look up the current state of a gate by its `GateId`.
+    pub fn gate(&self, id: GateId) -> Option<&Gate<T>> {
+        self.gates.get(&id)
+    }
+
+    /// The output gates, beginning with the root followed by every
+    /// gate promoted by `add_output` in the order it was promoted.
+    pub fn outputs(&self) -> Vec<GateId> {
+        self.outputs.clone()
+    }
+
+    /// Every gate of the reducer, ordered by `GateId`.
+    pub fn gates(&self) -> Vec<&Gate<T>> {
+        self.gates.values().collect()
+    }
+
+    /// The gates directly beneath `id`, whether added by `add_gate`
+    /// or by `short`.
+    pub fn children(&self, id: GateId) -> Option<&BTreeSet<GateId>> {
+        self.dag.connections(id)
+    }
+
+    /// The gates directly above `id`.
+    pub fn parents(&self, id: GateId) -> BTreeSet<GateId> {
+        self._get_parents(id)
+    }
+
+    /// The leaf gates, in the order their inputs are transitioned.
+    pub fn input_gates(&self) -> Vec<GateId> {
+        self._get_input_contacts()
+    }
+
+    /// Adds the gate described by `b` without resolving the reducer.
+    /// This allows a circuit to be built with its initial state in a
+    /// single pass, the reducer is then resolved once on the next call
+    /// to `output`.
+    pub fn add_gate_with(&mut self, b: GateBuilder<T>) -> Result<GateId, Error> {
+        let id: GateId = self._add_gate(b)?;
+        self.stale = true;
+        Ok(id)
+    }
+
+    /// Discards the stored outputs, so that the next call to `output`
+    /// resolves every gate from the input gates up rather than reading
+    /// the outputs propagated by each transition. Transitions are not
+    /// propagated until then.
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    fn _add_gate(&mut self, b: GateBuilder<T>) -> Result<GateId, Error> {
+        if !self.gates.contains_key(&b.parent) {
+            return Err(Error::VertexDoesNotExist);
+        }
+        // Identifiers are never reused, even once a gate has been removed,
+        // so that a stale handle cannot silently alias a new gate.
+        let contact: Gate<T> = Gate {
+            id: self.next,
+            input: b.input,
+            configuration: b.configuration,
+            program: b.program,
+            label: b.label,
         };
-        dag.add_vertex(contact_zero);
-        BTreeReducer { dag }
+        let id: GateId = contact.id;
+        self.next = GateId(id.0 + 1);
+        self.dag.add_vertex(id);
+        self.parents.insert(id, BTreeSet::new());
+        self.gates.insert(id, contact);
+        self._add_edge(b.parent, id)?;
+        Ok(id)
+    }
+
+    /// Look up a gate by its label. Labels are expected to be unique,
+    /// if they are not the gate with the lowest `GateId` is returned.
+    pub fn gate_named(&self, name: &str) -> Option<GateId> {
+        self.gates
+            .values()
+            .find(|g| -> bool { g.label() == Some(name) })
+            .map(|g| -> GateId { g.id })
     }
 
-    pub fn root(&self) -> Gate<T> {
-        let vertices: Vec<Gate<T>> = self.dag.vertices().into_iter().cloned().collect();
-        vertices[0].clone()
+    /// The input of every labeled input gate, keyed by label.
+    pub fn input_named(&self) -> BTreeMap<String, T> {
+        self._get_input_contacts()
+            .into_iter()
+            .filter_map(|id| -> Option<(String, T)> {
+                let gate: &Gate<T> = &self.gates[&id];
+                gate.label
+                    .clone()
+                    .map(|l| -> (String, T) { (l, gate.input()) })
+            })
+            .collect()
     }
 
-    pub fn update(&mut self, p: Gate<T>, u: Gate<T>)
+    /// Transitions the input gates named by the keys of `iv`, leaving
+    /// every other input unchanged. Unlike `Reinput<Vec<T>>` this does
+    /// not depend on the order in which the gates were inserted. No
+    /// input is transitioned if any name is unknown or does not refer
+    /// to an input gate.
+    pub fn reinput_named(&mut self, iv: &BTreeMap<&str, T>) -> Result<(), Error>
     where
         Gate<T>: Output<T> + Transition<T>,
     {
-        let previous_parents: BTreeSet<Gate<T>> = self
-            .dag
-            .vertices()
-            .into_iter()
-            .cloned()
-            .map(|v| -> (Gate<T>, &BTreeSet<Gate<T>>) {
-                (v.clone(), self.dag.connections(v).unwrap())
+        let inputs: Vec<GateId> = self._get_input_contacts();
+        let mut updates: Vec<(GateId, T)> = Vec::new();
+        for (name, state) in iv.iter() {
+            let id: GateId = self.gate_named(name).ok_or(Error::VertexDoesNotExist)?;
+            if !inputs.contains(&id) {
+                return Err(Error::ImproperDimension);
+            }
+            updates.push((id, state.clone()));
+        }
+        let mut changed: BTreeSet<GateId> = BTreeSet::new();
+        for (id, state) in updates {
+            let vertex: &mut Gate<T> = self.gates.get_mut(&id).unwrap();
+            if vertex.input() != state {
+                vertex.reinput(state)?;
+                changed.insert(id);
+            }
+        }
+        self._propagate(changed);
+        Ok(())
+    }
+
+    /// The output of every labeled output gate, keyed by label.
+    pub fn output_named(&mut self) -> BTreeMap<String, T>
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        self._resolve();
+        self.outputs
+            .iter()
+            .filter_map(|id| -> Option<(String, T)> {
+                let gate: &Gate<T> = &self.gates[id];
+                gate.label
+                    .clone()
+                    .map(|l| -> (String, T) { (l, gate.clone().output()) })
             })
-            .filter(|t| -> bool { t.1.contains(&p) })
-            .map(|t| -> Gate<T> { t.0 })
-            .collect();
+            .collect()
+    }
+
+    /// Computes the output of the root for the input vector `iv`
+    /// without transitioning the stored state of any gate. The input
+    /// vector is ordered as for `Reinput<Vec<T>>`.
+    pub fn evaluate(&self, iv: &[T]) -> Result<T, Error>
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        let mut outputs: BTreeMap<GateId, T> = self._input_outputs(iv)?;
+        Ok(self._evaluate_branch(self.root(), &mut outputs))
+    }
+
+    /// Computes the output of every gate, including those beyond the
+    /// branch of the root, for the input vector `iv` without
+    /// transitioning the stored state of any gate; see `evaluate`.
+    pub(crate) fn evaluate_gates(&self, iv: &[T]) -> Result<BTreeMap<GateId, T>, Error>
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        let mut outputs: BTreeMap<GateId, T> = self._input_outputs(iv)?;
+        for id in self.gates.keys() {
+            self._evaluate_branch(*id, &mut outputs);
+        }
+        Ok(outputs)
+    }
+
+    /// The output of every input gate for the input vector `iv`.
+    fn _input_outputs(&self, iv: &[T]) -> Result<BTreeMap<GateId, T>, Error>
+    where
+        Gate<T>: Output<T>,
+    {
+        let inputs: Vec<GateId> = self._get_input_contacts();
+        if inputs.len() != iv.len() {
+            return Err(Error::ImproperDimension);
+        }
+        let mut outputs: BTreeMap<GateId, T> = BTreeMap::new();
+        for (id, state) in inputs.into_iter().zip(iv.iter().cloned()) {
+            let mut gate: Gate<T> = self.gates[&id].clone();
+            gate.reinput(state)?;
+            outputs.insert(id, gate.output());
+        }
+        Ok(outputs)
+    }
+
+    pub fn update(&mut self, id: GateId, u: Gate<T>) -> Result<(), Error>
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        if !self.gates.contains_key(&id) {
+            return Err(Error::VertexDoesNotExist);
+        }
+        // The handle is stable, so only the gate state is replaced;
+        // the edges of the DAG are left untouched.
+        self.gates.insert(id, Gate { id, ..u });
+        self._propagate(BTreeSet::from([id]));
+        Ok(())
+    }
 
-        // Get all the edges from the previous vertex;
-        // let result = self.dag.remove_vertex(c);
-        let removal = self.dag.remove_vertex(p);
-        self.dag.add_vertex(u.clone());
-        // Add children back.
-        if let Ok(previous_children) = removal {
-            for previous_child in previous_children {
-                self.dag.add_edge(u.clone(), previous_child).unwrap();
+    /// Re-evaluates every gate in `changed` along with all of their
+    /// ancestors. Each of these dirty gates is re-evaluated exactly once,
+    /// after all of its dirty children, so that a batch of changes costs
+    /// a single pass over the affected part of the reducer. The gates
+    /// which are not dirty are assumed to be resolved already.
+    fn _propagate(&mut self, changed: BTreeSet<GateId>)
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        // A stale reducer is resolved in full once its output is read.
+        if self.stale {
+            return;
+        }
+        let mut dirty: BTreeSet<GateId> = BTreeSet::new();
+        let mut stack: Vec<GateId> = changed.into_iter().collect();
+        while let Some(id) = stack.pop() {
+            if dirty.insert(id) {
+                stack.extend(self.parents[&id].iter());
             }
         }
-        // Add parents back.
-        for previous_parent in previous_parents {
-            self.dag
-                .add_edge(previous_parent.clone(), u.clone())
-                .unwrap();
-            self._resolve_branch(previous_parent);
+        // A dirty gate is ready once none of its children are dirty.
+        let mut pending: BTreeMap<GateId, usize> = dirty
+            .iter()
+            .map(|id| -> (GateId, usize) {
+                let children: &BTreeSet<GateId> = self.dag.connections(*id).unwrap();
+                (*id, children.intersection(&dirty).count())
+            })
+            .collect();
+        let mut ready: Vec<GateId> = pending
+            .iter()
+            .filter(|(_, n)| -> bool { **n == 0 })
+            .map(|(id, _)| -> GateId { *id })
+            .collect();
+        while let Some(id) = ready.pop() {
+            self._reevaluate(id);
+            for parent in self.parents[&id].iter() {
+                let n: &mut usize = pending.get_mut(parent).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    ready.push(*parent);
+                }
+            }
         }
     }
 
-    fn _get_input_contacts(&self) -> Vec<Gate<T>> {
-        self.dag
-            .vertices()
-            .into_iter()
+    /// Transitions the input of `id` from the current outputs of its
+    /// children. The input of a leaf gate is left unchanged.
+    fn _reevaluate(&mut self, id: GateId)
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        let c: &Gate<T> = &self.gates[&id];
+        let contacts: &BTreeSet<GateId> = self.dag.connections(id).unwrap();
+        if contacts.is_empty() {
+            return;
+        }
+        let state_set: bool = contacts
+            .iter()
+            .any(|contact| -> bool { self.gates[contact].clone().output() != c.program() });
+        let reinput: T = if state_set {
+            c.transition()
+        } else {
+            c.program()
+        };
+        if c.input() != reinput {
+            self.gates.get_mut(&id).unwrap().reinput(reinput).unwrap();
+        }
+    }
+
+    fn _get_parents(&self, id: GateId) -> BTreeSet<GateId> {
+        self.parents.get(&id).cloned().unwrap_or_default()
+    }
+
+    fn _get_descendants(&self, id: GateId) -> BTreeSet<GateId> {
+        let mut descendants: BTreeSet<GateId> = BTreeSet::new();
+        let mut stack: Vec<GateId> = Vec::new();
+        stack.push(id);
+        while let Some(v) = stack.pop() {
+            for child in self.dag.connections(v).into_iter().flatten() {
+                if descendants.insert(*child) {
+                    stack.push(*child);
+                }
+            }
+        }
+        descendants
+    }
+
+    fn _get_input_contacts(&self) -> Vec<GateId> {
+        self.gates
+            .keys()
+            .filter(|c| -> bool { self.dag.connections(**c).unwrap().is_empty() })
             .cloned()
-            .filter(|c| -> bool { self.dag.connections(c.clone()).unwrap().is_empty() })
             .collect()
     }
 
-    fn _resolve_branch(&mut self, c: Gate<T>) -> T
+    /// The pure counterpart of `_resolve_gate`; the output of every
+    /// gate visited is recorded in `outputs` rather than in the gate.
+    fn _evaluate_branch(&self, id: GateId, outputs: &mut BTreeMap<GateId, T>) -> T
     where
         Gate<T>: Output<T> + Transition<T>,
     {
-        let mut final_state: T = c.clone().output();
-        if let Some(contacts) = self.dag.connections(c.clone()) {
-            if !contacts.is_empty() {
-                let mut state_set: bool = false;
-                for contact in contacts.clone() {
-                    if self._resolve_branch(contact) != c.program() && !state_set {
-                        state_set = true;
-                    }
-                }
-                // If the determined state is not equal to the current state,
-                // update the current state with the determined state.
-                let reinput: T = if state_set { c.transition() } else { c.program() };
-                if c.input() != reinput {
-                    let mut updated_c: Gate<T> = c.clone();
-                    updated_c.reinput(reinput).unwrap();
-                    self.update(c, updated_c.clone());
-                    final_state = updated_c.output();
-                }
+        if let Some(output) = outputs.get(&id) {
+            return output.clone();
+        }
+        let mut c: Gate<T> = self.gates[&id].clone();
+        let mut state_set: bool = false;
+        for contact in self.dag.connections(id).into_iter().flatten() {
+            if self._evaluate_branch(*contact, outputs) != c.program() {
+                state_set = true;
+                break;
             }
         }
+        let reinput: T = if state_set {
+            c.transition()
+        } else {
+            c.program()
+        };
+        c.reinput(reinput).unwrap();
+        let output: T = c.output();
+        outputs.insert(id, output.clone());
+        output
+    }
+
+    /// Resolves every gate if the stored state is stale. Every gate is
+    /// resolved at most once, however many parents it has been shorted
+    /// to.
+    fn _resolve(&mut self)
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        if !self.stale {
+            return;
+        }
+        let mut outputs: BTreeMap<GateId, T> = BTreeMap::new();
+        let mut changed: BTreeSet<GateId> = BTreeSet::new();
+        let ids: Vec<GateId> = self.gates.keys().cloned().collect();
+        for id in ids {
+            self._resolve_gate(id, &mut outputs, &mut changed);
+        }
+        self.stale = false;
+    }
+
+    /// The stored output of `id`, which is current once the reducer has
+    /// been resolved.
+    fn _output(&self, id: GateId) -> T
+    where
+        Gate<T>: Output<T>,
+    {
+        self.gates[&id].clone().output()
+    }
+
+    fn _resolve_gate(
+        &mut self,
+        id: GateId,
+        outputs: &mut BTreeMap<GateId, T>,
+        changed: &mut BTreeSet<GateId>,
+    ) -> T
+    where
+        Gate<T>: Output<T> + Transition<T>,
+    {
+        if let Some(output) = outputs.get(&id) {
+            return output.clone();
+        }
+        let contacts: BTreeSet<GateId> = self.dag.connections(id).cloned().unwrap_or_default();
         // If there are no adjacent vertices, then this node is a leaf node;
         // the state is simply the output of the contact's XOR gate.
-        final_state
+        if !contacts.is_empty() {
+            let program: T = self.gates[&id].program();
+            let mut state_set: bool = false;
+            for contact in contacts {
+                if self._resolve_gate(contact, outputs, changed) != program {
+                    state_set = true;
+                }
+            }
+            // If the determined state is not equal to the current state,
+            // update the current state with the determined state.
+            let c: &mut Gate<T> = self.gates.get_mut(&id).unwrap();
+            let reinput: T = if state_set {
+                c.transition()
+            } else {
+                c.program()
+            };
+            if c.input() != reinput {
+                c.reinput(reinput).unwrap();
+                changed.insert(id);
+            }
+        }
+        let output: T = self.gates[&id].clone().output();
+        outputs.insert(id, output.clone());
+        output
     }
 }
 
-impl<T> AddGate<Gate<T>> for BTreeReducer<T>
+// The edges of the DAG are only ever changed through these methods so
+// that `parents` is kept in step with `dag`.
+impl<T> BTreeReducer<T>
+where
+    T: Default + Ord + Clone,
+{
+    /// The parents of every gate, computed in a single pass over the DAG.
+    /// This is only needed to rebuild `parents` from scratch.
+    fn _get_parent_map(&self) -> BTreeMap<GateId, BTreeSet<GateId>> {
+        let mut parents: BTreeMap<GateId, BTreeSet<GateId>> = self
+            .gates
+            .keys()
+            .map(|id| -> (GateId, BTreeSet<GateId>) { (*id, BTreeSet::new()) })
+            .collect();
+        for v in self.dag.vertices() {
+            for child in self.dag.connections(*v).into_iter().flatten() {
+                parents.get_mut(child).unwrap().insert(*v);
+            }
+        }
+        parents
+    }
+
+    fn _add_edge(&mut self, x: GateId, y: GateId) -> Result<BTreeSet<GateId>, Error> {
+        let children: BTreeSet<GateId> = self.dag.add_edge(x, y)?;
+        self.parents.get_mut(&y).unwrap().insert(x);
+        Ok(children)
+    }
+
+    fn _remove_edge(&mut self, x: GateId, y: GateId) -> Result<BTreeSet<GateId>, Error> {
+        let children: BTreeSet<GateId> = self.dag.remove_edge(x, y)?;
+        self.parents.get_mut(&y).unwrap().remove(&x);
+        Ok(children)
+    }
+
+    /// Removes the gate `id` and all of its edges, returning its children.
+    fn _remove_vertex(&mut self, id: GateId) -> Result<BTreeSet<GateId>, Error> {
+        let children: BTreeSet<GateId> = self.dag.connections(id).cloned().unwrap_or_default();
+        self.dag.remove_vertex(id)?;
+        for child in children.iter() {
+            self.parents.get_mut(child).unwrap().remove(&id);
+        }
+        self.parents.remove(&id);
+        self.gates.remove(&id);
+        Ok(children)
+    }
+}
+
+impl<T> AddGate<GateId> for BTreeReducer<T>
 where
     T: Clone + Ord + Default,
     Gate<T>: Output<T> + Transition<T>,
 {
-    fn add_gate(&mut self, c: Gate<T>) -> Gate<T>
-    {
-        let vertices: Vec<&Gate<T>> = self.dag.vertices().into_iter().collect();
-        let contact: Gate<T> = Gate {
-            id: vertices[vertices.len() - 1].id + 1,
-            input: T::default(),
-            configuration: T::default(),
-            program: T::default(),
-        };
-        self.dag.add_vertex(contact.clone());
-        self.dag.add_edge(c, contact.clone()).unwrap();
-        self._resolve_branch(self.root());
-        contact
+    type Error = Error;
+    fn add_gate(&mut self, p: GateId) -> Result<GateId, Self::Error> {
+        let id: GateId = self._add_gate(GateBuilder::new(p))?;
+        // A new leaf only changes the outputs of its ancestors.
+        self._propagate(BTreeSet::from([id]));
+        Ok(id)
+    }
+}
+
+impl<T> Short<GateId> for BTreeReducer<T>
+where
+    T: Clone + Ord + Default,
+{
+    type Error = Error;
+    fn short(&mut self, x: GateId, y: GateId) -> Result<BTreeSet<GateId>, Self::Error> {
+        let children: BTreeSet<GateId> = self._add_edge(x, y)?;
+        self.stale = true;
+        Ok(children)
+    }
+}
+
+impl<T> RemoveShort<GateId> for BTreeReducer<T>
+where
+    T: Clone + Ord + Default,
+{
+    type Error = Error;
+    fn remove_short(&mut self, x: GateId, y: GateId) -> Result<BTreeSet<GateId>, Error> {
+        let children: BTreeSet<GateId> = self._remove_edge(x, y)?;
+        self.stale = true;
+        Ok(children)
     }
 }
 
-impl<T> Short<Gate<T>> for BTreeReducer<T>
+impl<T> AddOutput<GateId> for BTreeReducer<T>
 where
     T: Clone + Ord + Default,
-    Gate<T>: Output<T>,
 {
     type Error = Error;
-    fn short(&mut self, x: Gate<T>, y: Gate<T>) -> Result<BTreeSet<Gate<T>>, Self::Error> {
-        self.dag.add_edge(x, y)
+    fn add_output(&mut self, g: GateId) -> Result<(), Self::Error> {
+        if !self.gates.contains_key(&g) {
+            return Err(Error::VertexDoesNotExist);
+        }
+        if !self.outputs.contains(&g) {
+            self.outputs.push(g);
+        }
+        Ok(())
     }
 }
 
-impl<T> RemoveShort<Gate<T>> for BTreeReducer<T>
+impl<T> RemoveGate<GateId> for BTreeReducer<T>
 where
     T: Clone + Ord + Default,
+    Gate<T>: Output<T> + Transition<T>,
 {
     type Error = Error;
-    fn remove_short(&mut self, x: Gate<T>, y: Gate<T>) -> Result<BTreeSet<Gate<T>>, Error> {
-        self.dag.remove_edge(x, y)
+    /// Removes the gate `g`. If `descendants` is set, every descendant
+    /// of `g` that is not also reachable through another parent is
+    /// removed along with it; otherwise the children of `g` are shorted
+    /// to each of its parents. The root gate cannot be removed as the
+    /// output of the reducer always has a dimension of at least one (1);
+    /// any other output gate which is removed is no longer an output.
+    ///
+    /// Returns the input gates which no longer exist as a result.
+    fn remove_gate(&mut self, g: GateId, descendants: bool) -> Result<BTreeSet<GateId>, Error> {
+        if g == self.root() {
+            return Err(Error::ImproperDimension);
+        }
+        if !self.gates.contains_key(&g) {
+            return Err(Error::VertexDoesNotExist);
+        }
+        let previous_inputs: BTreeSet<GateId> = self._get_input_contacts().into_iter().collect();
+        let previous_parents: BTreeSet<GateId> = self._get_parents(g);
+        let previous_children: BTreeSet<GateId> =
+            self.dag.connections(g).cloned().unwrap_or_default();
+        // Every change is planned before the reducer is touched, so that
+        // an error cannot leave a gate half removed.
+        let mut removed: BTreeSet<GateId> = BTreeSet::from([g]);
+        let mut reconnections: Vec<(GateId, GateId)> = Vec::new();
+        if descendants {
+            // Remove descendants in waves; a descendant is exclusively
+            // owned once all of its parents have been removed.
+            let mut candidates: BTreeSet<GateId> = self._get_descendants(g);
+            loop {
+                let orphans: Vec<GateId> = candidates
+                    .iter()
+                    .filter(|c| -> bool { self._get_parents(**c).is_subset(&removed) })
+                    .cloned()
+                    .collect();
+                if orphans.is_empty() {
+                    break;
+                }
+                for orphan in orphans {
+                    candidates.remove(&orphan);
+                    removed.insert(orphan);
+                }
+            }
+        } else {
+            // The children of `g` are beneath each of its parents, so
+            // none of these edges can introduce a cycle; those which are
+            // already shorted are skipped.
+            for previous_parent in previous_parents.iter() {
+                let children: &BTreeSet<GateId> = self.dag.connections(*previous_parent).unwrap();
+                for previous_child in previous_children.iter() {
+                    if !children.contains(previous_child) {
+                        reconnections.push((*previous_parent, *previous_child));
+                    }
+                }
+            }
+        }
+        for id in removed {
+            self._remove_vertex(id)?;
+        }
+        for (x, y) in reconnections {
+            self._add_edge(x, y)?;
+        }
+        self._propagate(previous_parents);
+        let gates: &BTreeMap<GateId, Gate<T>> = &self.gates;
+        self.outputs.retain(|o| -> bool { gates.contains_key(o) });
+        let inputs: BTreeSet<GateId> = self._get_input_contacts().into_iter().collect();
+        Ok(previous_inputs.difference(&inputs).cloned().collect())
     }
 }
 
@@ -259,8 +835,7 @@ where
     fn input(&self) -> Vec<T> {
         self._get_input_contacts()
             .iter()
-            .cloned()
-            .map(|c| -> T { c.input() })
+            .map(|c| -> T { self.gates[c].input() })
             .collect()
     }
 }
@@ -307,14 +882,31 @@ where
 {
     type Error = Error;
     fn output(&mut self) -> T {
-        self._resolve_branch(self.root())
+        self._resolve();
+        self._output(self.root())
+    }
+}
+
+impl<T> Output<Vec<T>> for BTreeReducer<T>
+where
+    T: Clone + Ord + Default,
+    Gate<T>: Output<T> + Transition<T>,
+{
+    type Error = Error;
+    fn output(&mut self) -> Vec<T> {
+        self._resolve();
+        self.outputs
+            .iter()
+            .map(|o| -> T { self._output(*o) })
+            .collect()
     }
 }
 
 impl Output<String> for BTreeReducer<bool> {
     type Error = Error;
     fn output(&mut self) -> String {
-        if self._resolve_branch(self.root()) {
+        self._resolve();
+        if self._output(self.root()) {
             String::from("1")
         } else {
             String::from("0")
@@ -329,17 +921,21 @@ where
 {
     type Error = Error;
     fn reinput(&mut self, iv: Vec<T>) -> Result<(), Self::Error> {
-        let input: Vec<T> = self.input();
-        if input.dimension() != iv.dimension() {
+        let inputs: Vec<GateId> = self._get_input_contacts();
+        if inputs.dimension() != iv.dimension() {
             return Err(Error::ImproperDimension);
         }
-        for (vertex, state) in self._get_input_contacts().iter().cloned().zip(iv) {
+        // Only the ancestors of the leaves which actually changed are
+        // re-evaluated, once all of the leaves have been transitioned.
+        let mut changed: BTreeSet<GateId> = BTreeSet::new();
+        for (id, state) in inputs.into_iter().zip(iv) {
+            let vertex: &mut Gate<T> = self.gates.get_mut(&id).unwrap();
             if vertex.input() != state {
-                let mut updated_vertex = vertex.clone();
-                updated_vertex.reinput(state)?;
-                self.update(vertex.clone(), updated_vertex);
+                vertex.reinput(state)?;
+                changed.insert(id);
             }
         }
+        self._propagate(changed);
         Ok(())
     }
 }
@@ -360,9 +956,8 @@ where
     T: Clone + Ord + Default,
 {
     fn configuration(&self) -> Vec<T> {
-        self.dag
-            .vertices()
-            .into_iter()
+        self.gates
+            .values()
             .map(|c| -> T { c.configuration() })
             .collect()
     }
@@ -379,11 +974,7 @@ where
     T: Clone + Ord + Default,
 {
     fn program(&self) -> Vec<T> {
-        self.dag
-            .vertices()
-            .into_iter()
-            .map(|c| -> T { c.program() })
-            .collect()
+        self.gates.values().map(|c| -> T { c.program() }).collect()
     }
 }
 
@@ -404,13 +995,14 @@ where
         if configuration.dimension() != cv.dimension() {
             return Err(Error::ImproperDimension);
         }
-        for (vertex, state) in self.dag.clone().vertices().into_iter().cloned().zip(cv) {
+        let mut changed: BTreeSet<GateId> = BTreeSet::new();
+        for (vertex, state) in self.gates.values_mut().zip(cv) {
             if vertex.configuration() != state {
-                let mut updated_vertex = vertex.clone();
-                updated_vertex.reconfigure(state)?;
-                self.update(vertex.clone(), updated_vertex);
+                vertex.reconfigure(state)?;
+                changed.insert(vertex.id);
             }
         }
+        self._propagate(changed);
         Ok(())
     }
 }
@@ -437,13 +1029,14 @@ where
         if program.dimension() != pv.dimension() {
             return Err(Error::ImproperDimension);
         }
-        for (vertex, state) in self.dag.clone().vertices().into_iter().cloned().zip(pv) {
+        let mut changed: BTreeSet<GateId> = BTreeSet::new();
+        for (vertex, state) in self.gates.values_mut().zip(pv) {
             if vertex.program != state {
-                let mut updated_vertex = vertex.clone();
-                updated_vertex.reprogram(state)?;
-                self.update(vertex.clone(), updated_vertex);
+                vertex.reprogram(state)?;
+                changed.insert(vertex.id);
             }
         }
+        self._propagate(changed);
         Ok(())
     }
 }
@@ -0,0 +1,98 @@
+use crate::reducer::{BTreeReducer, Gate, GateId};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use btree_dag::{AddEdge, AddVertex, BTreeDAG, Connections, Vertices};
+use serde::de::Error as DeserializeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// `BTreeReducerSchema` is the serialized form of a `BTreeReducer`.
+/// The DAG is flattened into a list of edges, each of which is either
+/// an edge added by `add_gate` or a short. Encodings which predate
+/// `outputs` are decoded with the root as their only output.
+#[derive(Serialize, Deserialize)]
+struct BTreeReducerSchema<T>
+where
+    T: Default + Ord + Clone,
+{
+    gates: Vec<Gate<T>>,
+    edges: Vec<(GateId, GateId)>,
+    #[serde(default)]
+    outputs: Vec<GateId>,
+    next: GateId,
+}
+
+impl<T> Serialize for BTreeReducer<T>
+where
+    T: Serialize + Default + Ord + Clone,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut edges: Vec<(GateId, GateId)> = Vec::new();
+        for x in self.dag.vertices() {
+            for y in self.dag.connections(*x).into_iter().flatten() {
+                edges.push((*x, *y));
+            }
+        }
+        BTreeReducerSchema {
+            gates: self.gates.values().cloned().collect(),
+            edges,
+            outputs: self.outputs.clone(),
+            next: self.next,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for BTreeReducer<T>
+where
+    T: Deserialize<'de> + Default + Ord + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut schema: BTreeReducerSchema<T> = BTreeReducerSchema::deserialize(deserializer)?;
+        if schema.outputs.is_empty() {
+            schema.outputs.push(GateId::default());
+        }
+        let mut dag: BTreeDAG<GateId> = BTreeDAG::new();
+        let mut gates: BTreeMap<GateId, Gate<T>> = BTreeMap::new();
+        for gate in schema.gates {
+            if gate.id >= schema.next || gates.contains_key(&gate.id) {
+                return Err(D::Error::custom("gate identifiers must be unique"));
+            }
+            dag.add_vertex(gate.id);
+            gates.insert(gate.id, gate);
+        }
+        if !gates.contains_key(&GateId::default()) {
+            return Err(D::Error::custom("the root gate does not exist"));
+        }
+        if schema.outputs.first() != Some(&GateId::default())
+            || schema
+                .outputs
+                .iter()
+                .any(|o| -> bool { !gates.contains_key(o) })
+        {
+            return Err(D::Error::custom("outputs must begin with the root gate"));
+        }
+        // Re-adding the edges one at a time rejects any which refer to
+        // a missing gate or which would introduce a cycle.
+        for (x, y) in schema.edges {
+            dag.add_edge(x, y).map_err(|_| {
+                D::Error::custom("edges must connect existing gates without cycles")
+            })?;
+        }
+        let mut reducer: BTreeReducer<T> = BTreeReducer {
+            dag,
+            parents: BTreeMap::new(),
+            gates,
+            outputs: schema.outputs,
+            next: schema.next,
+            stale: true,
+        };
+        reducer.parents = reducer._get_parent_map();
+        Ok(reducer)
+    }
+}
@@ -1,12 +1,12 @@
 #[cfg(test)]
 mod unit_tests {
     use crate::reducer::api::{
-        AddGate, Configuration, Input, Output, Reconfigure, Reinput, RemoveShort, Reprogram, Short,
-        Transition,
+        AddGate, AddOutput, Configuration, Input, Output, Program, Reconfigure, Reinput,
+        RemoveGate, RemoveShort, Reprogram, Short, Transition,
     };
-    use crate::reducer::{BTreeReducer, Gate};
+    use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId};
     use crate::Error;
-    use alloc::collections::BTreeSet;
+    use alloc::collections::{BTreeMap, BTreeSet};
     use alloc::string::String;
     use alloc::vec::Vec;
 
@@ -43,59 +43,89 @@ mod unit_tests {
     #[test]
     fn root() {
         let reducer: BTreeReducer<bool> = BTreeReducer::new();
+        assert_eq!(reducer.root(), GateId(0));
         assert_eq!(
-            reducer.root(),
-            Gate {
-                id: 0,
+            reducer.gate(reducer.root()),
+            Some(&Gate {
+                id: GateId(0),
                 input: bool::default(),
                 configuration: bool::default(),
                 program: bool::default(),
-            }
+                label: None,
+            })
         );
     }
 
+    #[test]
+    fn gate() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series = reducer.add_gate(reducer.root())?;
+        assert_eq!(series, GateId(1));
+        assert_eq!(reducer.gate(series).unwrap().id(), series);
+        assert!(reducer.gate(GateId(2)).is_none());
+        assert!(reducer.add_gate(GateId(2)).is_err());
+
+        // The handle remains valid after the gate transitions.
+        let input = reducer.add_gate(series)?;
+        reducer.reinput(String::from("1"))?;
+        assert!(reducer.gate(input).unwrap().input());
+        reducer.reconfigure(String::from("001"))?;
+        assert!(reducer.gate(input).unwrap().configuration());
+        reducer.reprogram(String::from("010"))?;
+        assert!(reducer.gate(series).unwrap().program());
+        let parallel = reducer.add_gate(series)?;
+        reducer.short(parallel, input)?;
+        reducer.remove_short(parallel, input)?;
+        Ok(())
+    }
+
     #[test]
     fn update() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let mut root = reducer.root();
+        let mut root = reducer.gate(reducer.root()).unwrap().clone();
         assert!(!root.input());
         assert!(!root.configuration());
         assert!(!root.output());
 
-        let mut newroot = reducer.root();
+        let mut newroot = root.clone();
         newroot.reinput(true)?;
-        reducer.update(reducer.root(), newroot);
+        reducer.update(reducer.root(), newroot)?;
 
-        assert!(reducer.root().input());
-        assert!(!reducer.root().configuration());
-        assert!(reducer.root().output());
+        let mut root = reducer.gate(reducer.root()).unwrap().clone();
+        assert!(root.input());
+        assert!(!root.configuration());
+        assert!(root.output());
 
-        let mut newroot = reducer.root();
+        let mut newroot = root.clone();
         newroot.reinput(false)?;
-        reducer.update(reducer.root(), newroot);
+        reducer.update(reducer.root(), newroot)?;
 
-        let mut newroot = reducer.root();
+        let mut newroot = reducer.gate(reducer.root()).unwrap().clone();
         newroot.reconfigure(true)?;
-        reducer.update(reducer.root(), newroot);
+        reducer.update(reducer.root(), newroot)?;
 
-        assert!(!reducer.root().input());
-        assert!(reducer.root().configuration());
-        assert!(reducer.root().output());
+        let mut root = reducer.gate(reducer.root()).unwrap().clone();
+        assert!(!root.input());
+        assert!(root.configuration());
+        assert!(root.output());
 
-        let mut newroot = reducer.root();
+        let mut newroot = root.clone();
         newroot.reconfigure(false)?;
-        reducer.update(reducer.root(), newroot);
+        reducer.update(reducer.root(), newroot)?;
 
-        assert!(!reducer.root().input());
-        assert!(!reducer.root().configuration());
-        assert!(!reducer.root().output());
+        let mut root = reducer.gate(reducer.root()).unwrap().clone();
+        assert!(!root.input());
+        assert!(!root.configuration());
+        assert!(!root.output());
+
+        assert!(reducer.update(GateId(1), root).is_err());
         Ok(())
     }
 
     #[test]
     fn add_gate() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        reducer.add_gate(reducer.root());
+        reducer.add_gate(reducer.root())?;
 
         let input: Vec<bool> = reducer.input();
         assert_eq!(input.len(), 1);
@@ -109,7 +139,7 @@ mod unit_tests {
         let output: bool = reducer.output();
         assert!(!output);
 
-        let series = reducer.add_gate(reducer.root());
+        let series = reducer.add_gate(reducer.root())?;
 
         let input: Vec<bool> = reducer.input();
         assert_eq!(input.len(), 2);
@@ -125,7 +155,7 @@ mod unit_tests {
         let output: bool = reducer.output();
         assert!(!output);
 
-        reducer.add_gate(series);
+        reducer.add_gate(series)?;
 
         let input: Vec<bool> = reducer.input();
         assert_eq!(input.len(), 2);
@@ -147,7 +177,7 @@ mod unit_tests {
     #[test]
     fn reinput() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        reducer.add_gate(reducer.root());
+        reducer.add_gate(reducer.root())?;
 
         let input: Vec<bool> = reducer.input();
         assert_eq!(input.len(), 1);
@@ -183,8 +213,8 @@ mod unit_tests {
         assert!(!input[0]);
 
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        reducer.add_gate(reducer.root());
-        reducer.add_gate(reducer.root());
+        reducer.add_gate(reducer.root())?;
+        reducer.add_gate(reducer.root())?;
 
         let mut iv: Vec<bool> = Vec::new();
         iv.push(true);
@@ -250,7 +280,7 @@ mod unit_tests {
     #[test]
     fn reconfigure() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        reducer.add_gate(reducer.root());
+        reducer.add_gate(reducer.root())?;
 
         let configuration: Vec<bool> = reducer.configuration();
         assert_eq!(configuration.len(), 2);
@@ -323,9 +353,9 @@ mod unit_tests {
     #[test]
     fn and_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series = reducer.add_gate(reducer.root());
-        reducer.add_gate(series.clone());
-        reducer.add_gate(series);
+        let series = reducer.add_gate(reducer.root())?;
+        reducer.add_gate(series)?;
+        reducer.add_gate(series)?;
 
         let mut pv: Vec<bool> = Vec::new();
         pv.push(false);
@@ -414,9 +444,9 @@ mod unit_tests {
     #[test]
     fn nand_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series = reducer.add_gate(reducer.root());
-        reducer.add_gate(series.clone());
-        reducer.add_gate(series);
+        let series = reducer.add_gate(reducer.root())?;
+        reducer.add_gate(series)?;
+        reducer.add_gate(series)?;
 
         let mut pv: Vec<bool> = Vec::new();
         pv.push(false);
@@ -512,9 +542,9 @@ mod unit_tests {
     #[test]
     fn or_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let parallel = reducer.add_gate(reducer.root());
-        reducer.add_gate(parallel.clone());
-        reducer.add_gate(parallel);
+        let parallel = reducer.add_gate(reducer.root())?;
+        reducer.add_gate(parallel)?;
+        reducer.add_gate(parallel)?;
 
         let input: Vec<bool> = reducer.input();
         assert_eq!(input.len(), 2);
@@ -596,9 +626,9 @@ mod unit_tests {
     #[test]
     fn nor_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let parallel = reducer.add_gate(reducer.root());
-        reducer.add_gate(parallel.clone());
-        reducer.add_gate(parallel);
+        let parallel = reducer.add_gate(reducer.root())?;
+        reducer.add_gate(parallel)?;
+        reducer.add_gate(parallel)?;
 
         let input: Vec<bool> = reducer.input();
         assert_eq!(input.len(), 2);
@@ -709,12 +739,12 @@ mod unit_tests {
     #[test]
     fn xor_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series_0 = reducer.add_gate(reducer.root());
-        let parallel_1 = reducer.add_gate(series_0.clone());
-        let series_1 = reducer.add_gate(series_0.clone());
-        let input_0 = reducer.add_gate(parallel_1.clone());
-        let input_1 = reducer.add_gate(parallel_1.clone());
-        reducer.short(series_1.clone(), input_0)?;
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
         reducer.short(series_1, input_1)?;
 
         let mut pv: Vec<bool> = Vec::new();
@@ -840,12 +870,12 @@ mod unit_tests {
     #[test]
     fn xnor_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series_0 = reducer.add_gate(reducer.root());
-        let parallel_1 = reducer.add_gate(series_0.clone());
-        let series_1 = reducer.add_gate(series_0.clone());
-        let input_0 = reducer.add_gate(parallel_1.clone());
-        let input_1 = reducer.add_gate(parallel_1.clone());
-        reducer.short(series_1.clone(), input_0)?;
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
         reducer.short(series_1, input_1)?;
 
         let mut pv: Vec<bool> = Vec::new();
@@ -971,9 +1001,9 @@ mod unit_tests {
     #[test]
     fn and_truth_table_trans_nand_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series = reducer.add_gate(reducer.root());
-        reducer.add_gate(series.clone());
-        reducer.add_gate(series);
+        let series = reducer.add_gate(reducer.root())?;
+        reducer.add_gate(series)?;
+        reducer.add_gate(series)?;
 
         let mut pv: Vec<bool> = Vec::new();
         pv.push(false);
@@ -1150,12 +1180,12 @@ mod unit_tests {
     #[test]
     fn xor_truth_table_trans_xnor_truth_table() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series_0 = reducer.add_gate(reducer.root());
-        let parallel_1 = reducer.add_gate(series_0.clone());
-        let series_1 = reducer.add_gate(series_0.clone());
-        let input_0 = reducer.add_gate(parallel_1.clone());
-        let input_1 = reducer.add_gate(parallel_1.clone());
-        reducer.short(series_1.clone(), input_0)?;
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
         reducer.short(series_1, input_1)?;
 
         let mut pv: Vec<bool> = Vec::new();
@@ -1376,12 +1406,12 @@ mod unit_tests {
     #[test]
     fn xor_truth_table_trans_xnor_truth_table_string() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series_0 = reducer.add_gate(reducer.root());
-        let parallel_1 = reducer.add_gate(series_0.clone());
-        let series_1 = reducer.add_gate(series_0.clone());
-        let input_0 = reducer.add_gate(parallel_1.clone());
-        let input_1 = reducer.add_gate(parallel_1.clone());
-        reducer.short(series_1.clone(), input_0)?;
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
         reducer.short(series_1, input_1)?;
 
         let ps: String = String::from("010100");
@@ -1528,9 +1558,9 @@ mod unit_tests {
         }
 
         let mut reducer: BTreeReducer<char> = BTreeReducer::new();
-        reducer.add_gate(reducer.root());
-        reducer.add_gate(reducer.root());
-        reducer.add_gate(reducer.root());
+        reducer.add_gate(reducer.root())?;
+        reducer.add_gate(reducer.root())?;
+        reducer.add_gate(reducer.root())?;
 
         let mut input: Vec<char> = Vec::new();
         input.push('f');
@@ -1545,7 +1575,8 @@ mod unit_tests {
         program.push('\0');
         reducer.reprogram(program)?;
 
-        assert_eq!(reducer.output(), 'y');
+        let output: char = reducer.output();
+        assert_eq!(output, 'y');
 
         let mut input: Vec<char> = Vec::new();
         input.push('c');
@@ -1553,7 +1584,8 @@ mod unit_tests {
         input.push('t');
         reducer.reinput(input.clone())?;
 
-        assert_eq!(reducer.output(), 'y');
+        let output: char = reducer.output();
+        assert_eq!(output, 'y');
 
         let mut input: Vec<char> = Vec::new();
         input.push('p');
@@ -1561,7 +1593,8 @@ mod unit_tests {
         input.push('m');
         reducer.reinput(input.clone())?;
 
-        assert_eq!(reducer.output(), 'n');
+        let output: char = reducer.output();
+        assert_eq!(output, 'n');
 
         let mut input: Vec<char> = Vec::new();
         input.push('i');
@@ -1569,7 +1602,8 @@ mod unit_tests {
         input.push('m');
         reducer.reinput(input.clone())?;
 
-        assert_eq!(reducer.output(), 'y');
+        let output: char = reducer.output();
+        assert_eq!(output, 'y');
 
         let mut input: Vec<char> = Vec::new();
         input.push('d');
@@ -1577,7 +1611,8 @@ mod unit_tests {
         input.push('g');
         reducer.reinput(input.clone())?;
 
-        assert_eq!(reducer.output(), 'y');
+        let output: char = reducer.output();
+        assert_eq!(output, 'y');
 
         let mut input: Vec<char> = Vec::new();
         input.push('t');
@@ -1585,7 +1620,8 @@ mod unit_tests {
         input.push('s');
         reducer.reinput(input.clone())?;
 
-        assert_eq!(reducer.output(), 'n');
+        let output: char = reducer.output();
+        assert_eq!(output, 'n');
 
         Ok(())
     }
@@ -1593,14 +1629,454 @@ mod unit_tests {
     #[test]
     fn remove_short() -> Result<(), Error> {
         let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
-        let series_0 = reducer.add_gate(reducer.root());
-        let parallel_1 = reducer.add_gate(series_0.clone());
-        let series_1 = reducer.add_gate(series_0.clone());
-        let input_0 = reducer.add_gate(parallel_1.clone());
-        let input_1 = reducer.add_gate(parallel_1.clone());
-        reducer.short(series_1.clone(), input_0)?;
-        reducer.short(series_1.clone(), input_1.clone())?;
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
         reducer.remove_short(series_1, input_1)?;
         Ok(())
     }
+
+    #[test]
+    fn remove_gate() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+
+        assert!(reducer.remove_gate(reducer.root(), true).is_err());
+        assert!(reducer.remove_gate(GateId(6), true).is_err());
+
+        // The inputs are shared with parallel_1 and so are not removed.
+        let removed: BTreeSet<GateId> = reducer.remove_gate(series_1, true)?;
+        assert!(removed.is_empty());
+        assert!(reducer.gate(series_1).is_none());
+        let input: Vec<bool> = reducer.input();
+        assert_eq!(input.len(), 2);
+        let configuration: Vec<bool> = reducer.configuration();
+        assert_eq!(configuration.len(), 5);
+
+        // The inputs are now exclusively owned by parallel_1.
+        let removed: BTreeSet<GateId> = reducer.remove_gate(parallel_1, true)?;
+        assert_eq!(removed.len(), 2);
+        assert!(removed.contains(&input_0));
+        assert!(removed.contains(&input_1));
+        let input: Vec<bool> = reducer.input();
+        assert_eq!(input.len(), 1);
+        let configuration: Vec<bool> = reducer.configuration();
+        assert_eq!(configuration.len(), 2);
+
+        // Identifiers of removed gates are not reused.
+        let series = reducer.add_gate(series_0)?;
+        assert_eq!(series, GateId(6));
+
+        // Without descendants the children are shorted to the parent.
+        let input_0 = reducer.add_gate(series)?;
+        let input_1 = reducer.add_gate(series)?;
+        reducer.reinput(String::from("11"))?;
+        reducer.reprogram(String::from("00000"))?;
+        let output: bool = reducer.output();
+        assert!(output);
+
+        let removed: BTreeSet<GateId> = reducer.remove_gate(series, false)?;
+        assert!(removed.is_empty());
+        assert!(reducer.gate(series).is_none());
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), "11");
+        let output: bool = reducer.output();
+        assert!(output);
+
+        let removed: BTreeSet<GateId> = reducer.remove_gate(input_0, false)?;
+        assert_eq!(removed.len(), 1);
+        assert!(removed.contains(&input_0));
+        reducer.remove_gate(input_1, false)?;
+        let input: Vec<bool> = reducer.input();
+        assert_eq!(input.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_gate_failure() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series = reducer.add_gate(reducer.root())?;
+        let input_0 = reducer.add_gate(series)?;
+        let input_1 = reducer.add_gate(series)?;
+        reducer.short(reducer.root(), input_0)?;
+
+        // A failed removal leaves the reducer untouched.
+        let previous: BTreeReducer<bool> = reducer.clone();
+        assert!(reducer.remove_gate(reducer.root(), false).is_err());
+        assert_eq!(reducer, previous);
+        assert!(reducer.remove_gate(GateId(4), false).is_err());
+        assert_eq!(reducer, previous);
+
+        // input_0 is already shorted to the root, so only input_1 is
+        // reconnected.
+        reducer.remove_gate(series, false)?;
+        assert_eq!(
+            reducer.children(reducer.root()),
+            Some(&BTreeSet::from([input_0, input_1]))
+        );
+        assert_eq!(reducer.parents(input_0), BTreeSet::from([reducer.root()]));
+        assert_eq!(reducer.parents(input_1), BTreeSet::from([reducer.root()]));
+        Ok(())
+    }
+
+    #[test]
+    fn add_gate_with() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        assert!(reducer.add_gate_with(GateBuilder::new(GateId(1))).is_err());
+
+        // XOR
+        let series_0 = reducer.add_gate_with(GateBuilder::new(reducer.root()).program(true))?;
+        let parallel_1 = reducer.add_gate_with(GateBuilder::new(series_0).label("or"))?;
+        let series_1 = reducer.add_gate_with(
+            GateBuilder::new(series_0)
+                .program(true)
+                .configuration(true)
+                .label("nand"),
+        )?;
+        let input_0 = reducer.add_gate_with(GateBuilder::new(parallel_1).input(true).label("a"))?;
+        let input_1 = reducer.add_gate_with(GateBuilder::new(parallel_1).label("b"))?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+
+        assert_eq!(reducer.gate(parallel_1).unwrap().label(), Some("or"));
+        assert_eq!(reducer.gate(series_0).unwrap().label(), None);
+
+        let program: String = reducer.program();
+        assert_eq!(program.as_str(), "010100");
+
+        let configuration: String = reducer.configuration();
+        assert_eq!(configuration.as_str(), "000100");
+
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), "10");
+
+        let output: String = reducer.output();
+        assert_eq!(output.as_str(), "1");
+
+        reducer.reinput(String::from("11"))?;
+        let output: String = reducer.output();
+        assert_eq!(output.as_str(), "0");
+        Ok(())
+    }
+
+    #[test]
+    fn add_output() -> Result<(), Error> {
+        // Half adder
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let sum = reducer.add_gate_with(GateBuilder::new(reducer.root()).program(true))?;
+        let parallel = reducer.add_gate(sum)?;
+        let series =
+            reducer.add_gate_with(GateBuilder::new(sum).program(true).configuration(true))?;
+        let input_0 = reducer.add_gate(parallel)?;
+        let input_1 = reducer.add_gate(parallel)?;
+        reducer.short(series, input_0)?;
+        reducer.short(series, input_1)?;
+        let carry = reducer.add_gate_with(GateBuilder::new(reducer.root()).program(true))?;
+        reducer.short(carry, input_0)?;
+        reducer.short(carry, input_1)?;
+
+        assert!(reducer.add_output(GateId(9)).is_err());
+        reducer.add_output(sum)?;
+        reducer.add_output(carry)?;
+        reducer.add_output(carry)?;
+        assert_eq!(reducer.outputs(), Vec::from([reducer.root(), sum, carry]));
+
+        for (is, sum_bit, carry_bit) in [
+            ("00", false, false),
+            ("10", true, false),
+            ("01", true, false),
+            ("11", false, true),
+        ]
+        .iter()
+        {
+            reducer.reinput(String::from(*is))?;
+            let output: Vec<bool> = reducer.output();
+            assert_eq!(output.len(), 3);
+            assert_eq!(output[1], *sum_bit);
+            assert_eq!(output[2], *carry_bit);
+            let root: bool = reducer.output();
+            assert_eq!(output[0], root);
+        }
+
+        // Removing an output gate demotes it.
+        reducer.remove_gate(carry, false)?;
+        assert_eq!(reducer.outputs(), Vec::from([reducer.root(), sum]));
+        let output: Vec<bool> = reducer.output();
+        assert_eq!(output.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn named() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let mut root = reducer.gate(reducer.root()).unwrap().clone();
+        root.relabel("xor");
+        reducer.update(reducer.root(), root)?;
+        let series_0 = reducer.add_gate_with(GateBuilder::new(reducer.root()).program(true))?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate_with(
+            GateBuilder::new(series_0)
+                .program(true)
+                .configuration(true)
+                .label("nand"),
+        )?;
+        let input_0 = reducer.add_gate_with(GateBuilder::new(parallel_1).label("a"))?;
+        let input_1 = reducer.add_gate_with(GateBuilder::new(parallel_1).label("b"))?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+
+        assert_eq!(reducer.gate_named("a"), Some(input_0));
+        assert_eq!(reducer.gate_named("c"), None);
+
+        let mut iv: BTreeMap<&str, bool> = BTreeMap::new();
+        iv.insert("b", true);
+        reducer.reinput_named(&iv)?;
+
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), "01");
+
+        let input: BTreeMap<String, bool> = reducer.input_named();
+        assert_eq!(input.len(), 2);
+        assert!(!input["a"]);
+        assert!(input["b"]);
+
+        let output: BTreeMap<String, bool> = reducer.output_named();
+        assert_eq!(output.len(), 1);
+        assert!(output["xor"]);
+
+        // Unknown names and names of gates which are not inputs are
+        // rejected without transitioning any input.
+        let mut iv: BTreeMap<&str, bool> = BTreeMap::new();
+        iv.insert("a", true);
+        iv.insert("c", true);
+        assert!(reducer.reinput_named(&iv).is_err());
+        let mut iv: BTreeMap<&str, bool> = BTreeMap::new();
+        iv.insert("a", true);
+        iv.insert("nand", true);
+        assert!(reducer.reinput_named(&iv).is_err());
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), "01");
+
+        // Inserting another input does not shift the named inputs.
+        reducer.add_gate_with(GateBuilder::new(series_0).label("c"))?;
+        let mut iv: BTreeMap<&str, bool> = BTreeMap::new();
+        iv.insert("a", true);
+        iv.insert("b", false);
+        iv.insert("c", true);
+        reducer.reinput_named(&iv)?;
+        let input: BTreeMap<String, bool> = reducer.input_named();
+        assert!(input["a"]);
+        assert!(!input["b"]);
+        assert!(input["c"]);
+
+        reducer.add_output(series_1)?;
+        let output: BTreeMap<String, bool> = reducer.output_named();
+        assert_eq!(output.len(), 2);
+        assert!(output["xor"]);
+        assert!(output["nand"]);
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+        reducer.reprogram(String::from("010100"))?;
+        reducer.reconfigure(String::from("000100"))?;
+
+        let previous: BTreeReducer<bool> = reducer.clone();
+        assert!(!reducer.evaluate(&[false, false])?);
+        assert!(reducer.evaluate(&[true, false])?);
+        assert!(reducer.evaluate(&[false, true])?);
+        assert!(!reducer.evaluate(&[true, true])?);
+        assert!(reducer.evaluate(&[true]).is_err());
+        assert_eq!(reducer, previous);
+
+        for iv in [[false, false], [true, false], [false, true], [true, true]].iter() {
+            reducer.reinput(iv.to_vec())?;
+            let output: bool = reducer.output();
+            assert_eq!(reducer.evaluate(iv)?, output);
+        }
+
+        // A leaf gate is evaluated through its configuration.
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        reducer.reconfigure(String::from("1"))?;
+        assert!(reducer.evaluate(&[false])?);
+        assert!(!reducer.evaluate(&[true])?);
+        Ok(())
+    }
+
+    #[test]
+    fn propagate() -> Result<(), Error> {
+        // A chain of alternating AND and OR gates, with every gate of
+        // the chain also shorted to the first leaf.
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let mut parent: GateId = reducer.root();
+        let mut chain: Vec<GateId> = Vec::new();
+        for i in 0..8 {
+            parent = reducer.add_gate_with(GateBuilder::new(parent).program(i % 2 == 0))?;
+            chain.push(parent);
+        }
+        let mut leaves: Vec<GateId> = Vec::new();
+        for link in chain.iter() {
+            leaves.push(reducer.add_gate_with(GateBuilder::new(*link))?);
+        }
+        for link in chain.iter().skip(1) {
+            reducer.short(*link, leaves[0])?;
+        }
+        let _: bool = reducer.output();
+
+        for i in 0..(1 << leaves.len()) {
+            let iv: Vec<bool> = (0..leaves.len())
+                .map(|j| -> bool { i & (1 << j) != 0 })
+                .collect();
+            reducer.reinput(iv.clone())?;
+            // Every gate has already been re-evaluated by `reinput`, so
+            // fully resolving the reducer transitions nothing further.
+            let mut resolved: BTreeReducer<bool> = reducer.clone();
+            resolved.stale = true;
+            let output: bool = resolved.output();
+            assert_eq!(resolved, reducer);
+            assert_eq!(reducer.evaluate(&iv)?, output);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reconvergence() -> Result<(), Error> {
+        // A ladder of 48 levels of two gates, where both gates of every
+        // level are shorted to both gates of the level beneath. Without
+        // memoization the root reaches the leaves by 2^48 paths.
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let mut level: [GateId; 2] = [reducer.root(), reducer.root()];
+        for i in 0..48 {
+            let a: GateId =
+                reducer.add_gate_with(GateBuilder::new(level[0]).program(i % 2 == 0))?;
+            let b: GateId = reducer.add_gate_with(
+                GateBuilder::new(level[1])
+                    .program(i % 3 == 0)
+                    .configuration(i % 5 == 0),
+            )?;
+            if i > 0 {
+                reducer.short(level[0], b)?;
+                reducer.short(level[1], a)?;
+            }
+            level = [a, b];
+        }
+        assert_eq!(reducer.input_gates(), Vec::from(level));
+        let _: bool = reducer.output();
+
+        for iv in [[false, false], [true, false], [false, true], [true, true]].iter() {
+            reducer.reinput(iv.to_vec())?;
+            let mut resolved: BTreeReducer<bool> = reducer.clone();
+            resolved.stale = true;
+            let output: bool = resolved.output();
+            assert_eq!(resolved, reducer);
+            assert_eq!(reducer.evaluate(iv)?, output);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn stale() -> Result<(), Error> {
+        // OR
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let input_0 = reducer.add_gate(reducer.root())?;
+        let parallel = reducer.add_gate(reducer.root())?;
+        let input_1 = reducer.add_gate(parallel)?;
+        reducer.reinput(String::from("10"))?;
+        assert!(!reducer.stale);
+        let output: bool = reducer.output();
+        assert!(output);
+
+        // Detaching the set leaf clears the root and shorting it beneath
+        // another gate sets the root again, without any input being
+        // transitioned.
+        reducer.remove_short(reducer.root(), input_0)?;
+        assert!(reducer.stale);
+        let output: bool = reducer.output();
+        assert!(!output);
+        reducer.short(input_1, input_0)?;
+        assert!(reducer.stale);
+        let output: bool = reducer.output();
+        assert!(output);
+        assert!(!reducer.stale);
+        Ok(())
+    }
+
+    #[test]
+    fn invalidate() -> Result<(), Error> {
+        // OR
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        reducer.add_gate(reducer.root())?;
+        let parallel = reducer.add_gate(reducer.root())?;
+        reducer.add_gate(parallel)?;
+        reducer.reinput(String::from("01"))?;
+        let output: bool = reducer.output();
+        assert!(output);
+
+        // A transition of an invalidated reducer is only resolved once
+        // its output is read.
+        reducer.invalidate();
+        reducer.reinput(String::from("00"))?;
+        assert!(reducer.stale);
+        assert!(reducer.gate(reducer.root()).unwrap().clone().output());
+        let output: bool = reducer.output();
+        assert!(!output);
+        assert!(!reducer.stale);
+        reducer.reinput(String::from("10"))?;
+        assert!(reducer.gate(reducer.root()).unwrap().clone().output());
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_word() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+        reducer.reprogram(String::from("010100"))?;
+        reducer.reconfigure(String::from("000100"))?;
+
+        // Each bit of a word is a separate input vector.
+        assert_eq!(reducer.evaluate_word(&[0b1010u8, 0b1100u8])?, 0b0110u8);
+        assert_eq!(
+            reducer.evaluate_word(&[u128::MAX, 0x0f0f_0f0f_0f0f_0f0f_0f0f_0f0f_0f0f_0f0f])?,
+            0xf0f0_f0f0_f0f0_f0f0_f0f0_f0f0_f0f0_f0f0u128
+        );
+        assert!(reducer.evaluate_word(&[0u64]).is_err());
+
+        // XNOR
+        reducer.reconfigure(String::from("100100"))?;
+        let ivs: Vec<Vec<bool>> = (0..200)
+            .map(|i| -> Vec<bool> { Vec::from([i % 2 == 0, i % 3 == 0]) })
+            .collect();
+        let outputs: Vec<bool> = reducer.evaluate_many(&ivs)?;
+        assert_eq!(outputs.len(), ivs.len());
+        for (iv, output) in ivs.iter().zip(outputs) {
+            assert_eq!(reducer.evaluate(iv)?, output);
+        }
+        assert!(reducer.evaluate_many(&[Vec::from([true])]).is_err());
+        Ok(())
+    }
 }
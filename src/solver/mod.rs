@@ -0,0 +1,317 @@
+use crate::cnf::Cnf;
+use crate::reducer::api::Configuration;
+use crate::reducer::{BTreeReducer, GateId};
+use crate::Error;
+use alloc::vec;
+use alloc::vec::Vec;
+
+mod test;
+
+/// A literal of the variable `l / 2`, negated if `l` is odd.
+type Literal = usize;
+
+/// The number of conflicts before the first restart.
+const RESTART: usize = 100;
+
+/// `Solver` decides the satisfiability of a formula in conjunctive
+/// normal form by conflict-driven clause learning: it propagates unit
+/// clauses through two watched literals per clause, learns the first
+/// unique implication point of every conflict, backjumps, and decides
+/// the unassigned variable of highest activity, restarting every so
+/// often. Variables are numbered from one (1) as in `Cnf`.
+pub(crate) struct Solver {
+    clauses: Vec<Vec<Literal>>,
+    /// The clauses watching each literal, i.e. those of which it is one
+    /// of the first two literals.
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    /// The clause which implied each variable, none for a decision.
+    reasons: Vec<Option<usize>>,
+    /// The last value of each variable, decided again after backjumping.
+    phases: Vec<bool>,
+    activity: Vec<f64>,
+    increment: f64,
+    trail: Vec<Literal>,
+    /// The length of the trail before each decision.
+    limits: Vec<usize>,
+    head: usize,
+    unsatisfiable: bool,
+}
+
+impl Solver {
+    /// A solver for the conjunction of `clauses` over `variables`
+    /// variables, each clause a slice of non-zero literals.
+    pub(crate) fn new(variables: usize, clauses: &[Vec<isize>]) -> Self {
+        let mut solver: Solver = Solver {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * variables],
+            values: vec![None; variables],
+            levels: vec![0; variables],
+            reasons: vec![None; variables],
+            phases: vec![false; variables],
+            activity: vec![0.0; variables],
+            increment: 1.0,
+            trail: Vec::new(),
+            limits: Vec::new(),
+            head: 0,
+            unsatisfiable: false,
+        };
+        for clause in clauses.iter() {
+            let mut literals: Vec<Literal> = clause
+                .iter()
+                .map(|l| -> Literal { 2 * (l.unsigned_abs() - 1) + (*l < 0) as usize })
+                .collect();
+            literals.sort_unstable();
+            literals.dedup();
+            // A clause with both a literal and its negation always holds.
+            if literals.windows(2).any(|w| -> bool { w[0] ^ 1 == w[1] }) {
+                continue;
+            }
+            match literals.len() {
+                0 => solver.unsatisfiable = true,
+                1 => match solver.value(literals[0]) {
+                    Some(false) => solver.unsatisfiable = true,
+                    Some(true) => {}
+                    None => solver.assign(literals[0], None),
+                },
+                _ => {
+                    solver.add_clause(literals);
+                }
+            }
+        }
+        solver
+    }
+
+    /// A satisfying assignment of every variable, if there is one.
+    pub(crate) fn solve(&mut self) -> Option<Vec<bool>> {
+        if self.unsatisfiable {
+            return None;
+        }
+        let mut conflicts: usize = 0;
+        let mut restart: usize = RESTART;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.limits.is_empty() {
+                    self.unsatisfiable = true;
+                    return None;
+                }
+                let (learnt, level) = self.analyze(conflict);
+                self.backtrack(level);
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let asserting: Literal = learnt[0];
+                    let clause: usize = self.add_clause(learnt);
+                    self.assign(asserting, Some(clause));
+                }
+                self.increment /= 0.95;
+                conflicts += 1;
+                if conflicts == restart {
+                    self.backtrack(0);
+                    restart += restart / 2;
+                    conflicts = 0;
+                }
+                continue;
+            }
+            let decision: Option<usize> = (0..self.values.len())
+                .filter(|v| -> bool { self.values[*v].is_none() })
+                .fold(None, |best: Option<usize>, v| -> Option<usize> {
+                    match best {
+                        Some(b) if self.activity[b] >= self.activity[v] => Some(b),
+                        _ => Some(v),
+                    }
+                });
+            match decision {
+                Some(v) => {
+                    self.limits.push(self.trail.len());
+                    self.assign(2 * v + !self.phases[v] as usize, None);
+                }
+                None => {
+                    return Some(self.values.iter().map(|v| -> bool { v.unwrap() }).collect());
+                }
+            }
+        }
+    }
+
+    fn value(&self, literal: Literal) -> Option<bool> {
+        self.values[literal / 2].map(|v| -> bool { v != (literal & 1 == 1) })
+    }
+
+    fn assign(&mut self, literal: Literal, reason: Option<usize>) {
+        let v: usize = literal / 2;
+        self.values[v] = Some(literal & 1 == 0);
+        self.levels[v] = self.limits.len();
+        self.reasons[v] = reason;
+        self.trail.push(literal);
+    }
+
+    /// Adds a clause of at least two literals, watching the first two.
+    fn add_clause(&mut self, literals: Vec<Literal>) -> usize {
+        let clause: usize = self.clauses.len();
+        self.watches[literals[0]].push(clause);
+        self.watches[literals[1]].push(clause);
+        self.clauses.push(literals);
+        clause
+    }
+
+    /// Assigns every literal implied by a unit clause, returning the
+    /// clause which is falsified instead if there is one. The implied
+    /// literal of a clause is always its first.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.head < self.trail.len() {
+            let falsified: Literal = self.trail[self.head] ^ 1;
+            self.head += 1;
+            let mut watchers: Vec<usize> = core::mem::take(&mut self.watches[falsified]);
+            let mut i: usize = 0;
+            while i < watchers.len() {
+                let c: usize = watchers[i];
+                if self.clauses[c][0] == falsified {
+                    self.clauses[c].swap(0, 1);
+                }
+                let first: Literal = self.clauses[c][0];
+                if self.value(first) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+                // Watch another literal which is not false, if any.
+                let other: Option<usize> = (2..self.clauses[c].len())
+                    .find(|k| -> bool { self.value(self.clauses[c][*k]) != Some(false) });
+                if let Some(k) = other {
+                    self.clauses[c].swap(1, k);
+                    self.watches[self.clauses[c][1]].push(c);
+                    watchers.swap_remove(i);
+                    continue;
+                }
+                if self.value(first) == Some(false) {
+                    self.watches[falsified] = watchers;
+                    return Some(c);
+                }
+                self.assign(first, Some(c));
+                i += 1;
+            }
+            self.watches[falsified] = watchers;
+        }
+        None
+    }
+
+    /// Learns a clause from the falsified clause `conflict`, resolving
+    /// on the literals of the current level until only one remains. The
+    /// learnt clause begins with the negation of that literal, followed
+    /// by the literal of the level to backjump to, which is returned.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Literal>, usize) {
+        let level: usize = self.limits.len();
+        let mut seen: Vec<bool> = vec![false; self.values.len()];
+        let mut learnt: Vec<Literal> = Vec::from([0]);
+        let mut pending: usize = 0;
+        let mut index: usize = self.trail.len();
+        let mut clause: usize = conflict;
+        let mut skip: usize = 0;
+        loop {
+            for k in skip..self.clauses[clause].len() {
+                let literal: Literal = self.clauses[clause][k];
+                let v: usize = literal / 2;
+                if seen[v] || self.levels[v] == 0 {
+                    continue;
+                }
+                seen[v] = true;
+                self.bump(v);
+                if self.levels[v] == level {
+                    pending += 1;
+                } else {
+                    learnt.push(literal);
+                }
+            }
+            loop {
+                index -= 1;
+                if seen[self.trail[index] / 2] {
+                    break;
+                }
+            }
+            let literal: Literal = self.trail[index];
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = literal ^ 1;
+                break;
+            }
+            clause = self.reasons[literal / 2].unwrap();
+            skip = 1;
+        }
+        let mut backjump: usize = 0;
+        for k in 1..learnt.len() {
+            if self.levels[learnt[k] / 2] > backjump {
+                backjump = self.levels[learnt[k] / 2];
+                learnt.swap(1, k);
+            }
+        }
+        (learnt, backjump)
+    }
+
+    fn bump(&mut self, v: usize) {
+        self.activity[v] += self.increment;
+        if self.activity[v] > 1e100 {
+            for a in self.activity.iter_mut() {
+                *a *= 1e-100;
+            }
+            self.increment *= 1e-100;
+        }
+    }
+
+    /// Unassigns every literal of a level above `level`.
+    fn backtrack(&mut self, level: usize) {
+        if self.limits.len() <= level {
+            return;
+        }
+        let limit: usize = self.limits[level];
+        for literal in self.trail.drain(limit..) {
+            self.phases[literal / 2] = literal & 1 == 0;
+            self.values[literal / 2] = None;
+        }
+        self.limits.truncate(level);
+        self.head = limit;
+    }
+}
+
+impl BTreeReducer<bool> {
+    /// An input vector, ordered as for `Reinput<Vec<bool>>`, for which
+    /// the root outputs `desired`, if there is one; see
+    /// `find_input_with`.
+    pub fn find_input(&self, desired: bool) -> Option<Vec<bool>> {
+        let fixed: Vec<Option<bool>> = vec![None; self.input_gates().len()];
+        self.find_input_with(desired, &fixed).unwrap()
+    }
+
+    /// An input vector for which the root outputs `desired`, if there
+    /// is one, where every input `j` for which `fixed[j]` is set takes
+    /// that value. The reducer is encoded as by `cnf` and solved by an
+    /// in-crate CDCL solver, so unlike `truth_table` this is practical
+    /// for many inputs.
+    pub fn find_input_with(
+        &self,
+        desired: bool,
+        fixed: &[Option<bool>],
+    ) -> Result<Option<Vec<bool>>, Error> {
+        let inputs: Vec<GateId> = self.input_gates();
+        if fixed.len() != inputs.len() {
+            return Err(Error::ImproperDimension);
+        }
+        let mut cnf: Cnf = self.cnf(Some(desired));
+        for (id, value) in inputs.iter().zip(fixed.iter()) {
+            if let Some(value) = value {
+                cnf.assert(*id, *value != self.gate(*id).unwrap().configuration())?;
+            }
+        }
+        let model: Vec<bool> = match Solver::new(cnf.variables(), cnf.clauses()).solve() {
+            Some(model) => model,
+            None => return Ok(None),
+        };
+        Ok(Some(
+            inputs
+                .iter()
+                .map(|id| -> bool {
+                    model[cnf.variable(*id).unwrap() - 1] != self.gate(*id).unwrap().configuration()
+                })
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::expression::parse;
+    use crate::parse::ParseError;
+    use crate::reducer::api::{Output, Reinput};
+    use crate::reducer::BTreeReducer;
+    use crate::solver::Solver;
+    use crate::truth_table::TruthTable;
+    use crate::Error;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    /// Whether `assignment`, of which bit `v - 1` is variable `v`,
+    /// satisfies every clause.
+    fn satisfies(clauses: &[Vec<isize>], assignment: &[bool]) -> bool {
+        clauses.iter().all(|clause| -> bool {
+            clause
+                .iter()
+                .any(|l| -> bool { assignment[l.unsigned_abs() - 1] == (*l > 0) })
+        })
+    }
+
+    #[test]
+    fn solve_formulas() {
+        // Four pigeons do not fit in three holes.
+        let mut clauses: Vec<Vec<isize>> = Vec::new();
+        let hole = |p: isize, h: isize| -> isize { 3 * p + h + 1 };
+        for p in 0..4 {
+            clauses.push((0..3).map(|h| -> isize { hole(p, h) }).collect());
+        }
+        for h in 0..3 {
+            for p in 0..4 {
+                for q in p + 1..4 {
+                    clauses.push(Vec::from([-hole(p, h), -hole(q, h)]));
+                }
+            }
+        }
+        assert_eq!(Solver::new(12, &clauses).solve(), None);
+        clauses.remove(0);
+        let model: Vec<bool> = Solver::new(12, &clauses).solve().unwrap();
+        assert!(satisfies(&clauses, &model));
+
+        assert_eq!(Solver::new(1, &[Vec::new()]).solve(), None);
+        assert_eq!(
+            Solver::new(1, &[Vec::from([1]), Vec::from([-1])]).solve(),
+            None
+        );
+        assert_eq!(
+            Solver::new(2, &[Vec::from([1, -1]), Vec::from([-2, -2])]).solve(),
+            Some(Vec::from([false, false]))
+        );
+
+        // Random 3-SAT around the threshold, checked by brute force.
+        let mut seed: u64 = 1;
+        let mut random = |n: u64| -> u64 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+        for _ in 0..200 {
+            let variables: usize = 10;
+            let clauses: Vec<Vec<isize>> = (0..43)
+                .map(|_| -> Vec<isize> {
+                    (0..3)
+                        .map(|_| -> isize {
+                            let v: isize = random(variables as u64) as isize + 1;
+                            if random(2) == 0 {
+                                v
+                            } else {
+                                -v
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            let satisfiable: bool = (0..1usize << variables).any(|a| -> bool {
+                let assignment: Vec<bool> = (0..variables)
+                    .map(|v| -> bool { (a >> v) & 1 == 1 })
+                    .collect();
+                satisfies(&clauses, &assignment)
+            });
+            match Solver::new(variables, &clauses).solve() {
+                Some(model) => assert!(satisfies(&clauses, &model)),
+                None => assert!(!satisfiable),
+            }
+        }
+    }
+
+    #[test]
+    fn find_input() -> Result<(), ParseError> {
+        for s in [
+            "a",
+            "!a",
+            "a & !a",
+            "a | !a",
+            "a & !b",
+            "!(a | b) & (c | !d)",
+            "a ^ b ^ c",
+            "let x = a & b; x | !x & c",
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = parse(s)?;
+            let table: TruthTable = reducer.truth_table().unwrap();
+            for desired in [false, true].iter() {
+                match reducer.find_input(*desired) {
+                    Some(iv) => {
+                        let mut reducer: BTreeReducer<bool> = reducer.clone();
+                        reducer.reinput(iv).unwrap();
+                        let outputs: Vec<bool> = reducer.output();
+                        assert_eq!(outputs[0], *desired, "{}", s);
+                    }
+                    None => assert!(
+                        (0..table.rows()).all(|row| -> bool { table.get(row) != Some(*desired) }),
+                        "{}",
+                        s
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn find_input_with() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = parse("(a | b) & !c")?;
+        assert_eq!(
+            reducer.find_input_with(true, &[Some(false), None, None]),
+            Ok(Some(Vec::from([false, true, false])))
+        );
+        assert_eq!(
+            reducer.find_input_with(true, &[None, None, Some(true)]),
+            Ok(None)
+        );
+        assert_eq!(
+            reducer.find_input_with(false, &[Some(true), Some(true), None]),
+            Ok(Some(Vec::from([true, true, true])))
+        );
+        assert_eq!(
+            reducer.find_input_with(true, &[None, None]),
+            Err(Error::ImproperDimension)
+        );
+
+        // Far too many inputs for a truth table.
+        let names: Vec<String> = (0..80).map(|j| -> String { format!("x{}", j) }).collect();
+        let clauses: Vec<String> = (0..40)
+            .map(|j| -> String { format!("({} | !{})", names[2 * j], names[2 * j + 1]) })
+            .collect();
+        let implications: BTreeReducer<bool> = parse(&clauses.join(" & "))?;
+        let fixed: Vec<Option<bool>> = (0..80)
+            .map(|j| -> Option<bool> {
+                if j % 2 == 0 {
+                    Some(j % 4 == 0)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let iv: Vec<bool> = implications.find_input_with(true, &fixed).unwrap().unwrap();
+        for j in 0..40 {
+            assert!(iv[2 * j] || !iv[2 * j + 1]);
+            assert_eq!(iv[2 * j], j % 2 == 0);
+        }
+        let mut fixed: Vec<Option<bool>> = vec![None; 80];
+        fixed[78] = Some(false);
+        fixed[79] = Some(true);
+        assert_eq!(implications.find_input_with(true, &fixed), Ok(None));
+
+        let literals: Vec<String> = names
+            .iter()
+            .enumerate()
+            .map(|(j, name)| -> String {
+                if j % 3 == 0 {
+                    format!("!{}", name)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect();
+        let product: BTreeReducer<bool> = parse(&literals.join(" & "))?;
+        let iv: Vec<bool> = product.find_input(true).unwrap();
+        assert_eq!(
+            iv,
+            (0..80)
+                .map(|j| -> bool { j % 3 != 0 })
+                .collect::<Vec<bool>>()
+        );
+        let iv: Vec<bool> = product.find_input(false).unwrap();
+        let mut product: BTreeReducer<bool> = product.clone();
+        product.reinput(iv).unwrap();
+        let outputs: Vec<bool> = product.output();
+        assert_eq!(outputs, Vec::from([false]));
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use crate::reducer::api::{Output, Reconfigure, Short};
+use crate::reducer::{BTreeReducer, Gate, GateBuilder, GateId, Word};
+use crate::Error;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+mod test;
+
+/// `Cube` is a product of literals over the inputs of a truth table;
+/// input `j` appears as is if `cube[j]` is `Some(true)`, inverted if it
+/// is `Some(false)` and not at all if it is `None`.
+pub(crate) type Cube = Vec<Option<bool>>;
+
+/// The input words of the first 64 rows of a truth table. Bit `k` of
+/// the `j`th pattern is bit `j` of `k`.
+const PATTERNS: [u64; 6] = [
+    0xaaaa_aaaa_aaaa_aaaa,
+    0xcccc_cccc_cccc_cccc,
+    0xf0f0_f0f0_f0f0_f0f0,
+    0xff00_ff00_ff00_ff00,
+    0xffff_0000_ffff_0000,
+    0xffff_ffff_0000_0000,
+];
+
+/// `TruthTable` holds the output of a `BTreeReducer<bool>` for every
+/// one of its 2^n input vectors, packed 64 rows to a word. In row `i`
+/// the `j`th input, ordered as for `Reinput<Vec<bool>>`, is bit `j` of
+/// `i`; i.e. the first input changes fastest.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct TruthTable {
+    inputs: usize,
+    words: Vec<u64>,
+}
+
+impl TruthTable {
+    /// The most inputs a table may have. A table of this many inputs
+    /// already holds 2^24 rows in 2 MiB, and every row is evaluated
+    /// when it is built.
+    pub const MAX_INPUTS: usize = 24;
+
+    /// Builds the table of `inputs` inputs from the output of every row,
+    /// which must number exactly 2^`inputs`. No more than `MAX_INPUTS`
+    /// inputs are allowed.
+    pub fn from_bits(inputs: usize, bits: &[bool]) -> Result<Self, Error> {
+        if inputs > Self::MAX_INPUTS || bits.len() != 1 << inputs {
+            return Err(Error::ImproperDimension);
+        }
+        let mut words: Vec<u64> = vec![0; bits.len().div_ceil(64)];
+        for (row, bit) in bits.iter().enumerate() {
+            words[row / 64] |= (*bit as u64) << (row % 64);
+        }
+        Ok(TruthTable { inputs, words })
+    }
+
+    /// Builds the table of `inputs` inputs by calling `f` with the input
+    /// vector of every row. No more than `MAX_INPUTS` inputs are allowed.
+    pub fn from_fn<F>(inputs: usize, mut f: F) -> Result<Self, Error>
+    where
+        F: FnMut(Vec<bool>) -> bool,
+    {
+        if inputs > Self::MAX_INPUTS {
+            return Err(Error::ImproperDimension);
+        }
+        let mut words: Vec<u64> = vec![0; (1usize << inputs).div_ceil(64)];
+        for row in 0..1 << inputs {
+            let iv: Vec<bool> = (0..inputs)
+                .map(|j| -> bool { (row >> j) & 1 == 1 })
+                .collect();
+            words[row / 64] |= (f(iv) as u64) << (row % 64);
+        }
+        Ok(TruthTable { inputs, words })
+    }
+
+    /// The number of inputs, n.
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// The number of rows, 2^n.
+    pub fn rows(&self) -> usize {
+        1 << self.inputs
+    }
+
+    /// The output in row `row`, if the row exists.
+    pub fn get(&self, row: usize) -> Option<bool> {
+        if row >= self.rows() {
+            return None;
+        }
+        Some((self.words[row / 64] >> (row % 64)) & 1 == 1)
+    }
+
+    /// The input vector of row `row`.
+    pub fn input(&self, row: usize) -> Vec<bool> {
+        (0..self.inputs)
+            .map(|j| -> bool { (row >> j) & 1 == 1 })
+            .collect()
+    }
+
+    /// The packed outputs, 64 rows to a word. Any bits beyond the last
+    /// row are zero (0).
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The number of rows with an output of one (1).
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|w| -> usize { w.count_ones() as usize })
+            .sum()
+    }
+}
+
+/// Prints one row per line, the input vector followed by the output,
+/// e.g. `10 1`.
+impl fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows() {
+            for bit in self.input(row) {
+                write!(f, "{}", bit as u8)?;
+            }
+            if self.inputs > 0 {
+                write!(f, " ")?;
+            }
+            writeln!(f, "{}", self.get(row).unwrap() as u8)?;
+        }
+        Ok(())
+    }
+}
+
+impl BTreeReducer<bool> {
+    /// Enumerates every input vector of the reducer without transitioning
+    /// it. The table has 2^n rows, so a reducer of more than
+    /// `TruthTable::MAX_INPUTS` inputs is rejected.
+    pub fn truth_table(&self) -> Result<TruthTable, Error> {
+        let inputs: usize = self.input_gates().len();
+        if inputs > TruthTable::MAX_INPUTS {
+            return Err(Error::ImproperDimension);
+        }
+        let rows: usize = 1 << inputs;
+        let mut words: Vec<u64> = vec![0; rows.div_ceil(64)];
+        for (w, word) in words.iter_mut().enumerate() {
+            // The first six (6) inputs vary within a word, every other
+            // input is constant across it.
+            let iv: Vec<u64> = (0..inputs)
+                .map(|j| -> u64 {
+                    match PATTERNS.get(j) {
+                        Some(pattern) => *pattern,
+                        None => u64::splat((w >> (j - 6)) & 1 == 1),
+                    }
+                })
+                .collect();
+            *word = self.evaluate_word(&iv).unwrap();
+        }
+        if rows < 64 {
+            words[0] &= (1 << rows) - 1;
+        }
+        Ok(TruthTable { inputs, words })
+    }
+
+    /// Synthesizes a reducer which implements `t`, such that
+    /// `BTreeReducer::from_truth_table(&t)?.truth_table()? == t`. The
+    /// reducer is a parallel (OR) root above one series (AND) gate per
+    /// row, built from whichever of the rows with an output of one (1)
+    /// or zero (0) are fewer; in the latter case the root is inverted.
+    /// A table without inputs cannot be implemented as a reducer always
+    /// has at least one (1) input gate.
+    pub fn from_truth_table(t: &TruthTable) -> Result<Self, Error> {
+        if t.inputs() == 0 {
+            return Err(Error::ImproperDimension);
+        }
+        let invert: bool = t.count_ones() * 2 > t.rows();
+        let cubes: Vec<Cube> = (0..t.rows())
+            .filter(|row| -> bool { t.get(*row) != Some(invert) })
+            .map(|row| -> Cube { t.input(row).into_iter().map(Some).collect() })
+            .collect();
+        Ok(sum_of_products(t.inputs(), &cubes, invert))
+    }
+}
+
+/// Builds a reducer of `inputs` inputs whose output is the sum (OR) of
+/// `cubes`, inverted if `invert` is set. Each input is a single leaf;
+/// an inverted input is a single inverting gate above that leaf, shared
+/// by every cube in which it appears.
+pub(crate) fn sum_of_products(inputs: usize, cubes: &[Cube], invert: bool) -> BTreeReducer<bool> {
+    let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+    let mut root: Gate<bool> = reducer.gate(reducer.root()).unwrap().clone();
+    root.reconfigure(invert).unwrap();
+    reducer.update(reducer.root(), root).unwrap();
+
+    // The children of every term, as literals: (input, inverted).
+    let mut terms: Vec<(bool, Vec<(usize, bool)>)> = cubes
+        .iter()
+        .map(|cube| -> (bool, Vec<(usize, bool)>) {
+            let literals: Vec<(usize, bool)> = cube
+                .iter()
+                .enumerate()
+                .filter_map(|(j, l)| -> Option<(usize, bool)> {
+                    l.map(|b| -> (usize, bool) { (j, !b) })
+                })
+                .collect();
+            if literals.is_empty() {
+                // An empty product is one (1), i.e. x OR NOT x.
+                (false, Vec::from([(0, false), (0, true)]))
+            } else {
+                (true, literals)
+            }
+        })
+        .collect();
+    // Every input must be a leaf even if no cube refers to it, those
+    // which are unused hang beneath a term which is always zero (0),
+    // i.e. x AND NOT x AND ...
+    let unused: Vec<usize> = (0..inputs)
+        .filter(|j| -> bool {
+            !terms
+                .iter()
+                .any(|(_, ls)| -> bool { ls.iter().any(|l| -> bool { l.0 == *j }) })
+        })
+        .collect();
+    if !unused.is_empty() {
+        let mut literals: Vec<(usize, bool)> = Vec::from([(unused[0], false), (unused[0], true)]);
+        literals.extend(
+            unused
+                .iter()
+                .skip(1)
+                .map(|j| -> (usize, bool) { (*j, false) }),
+        );
+        terms.push((true, literals));
+    }
+
+    // A term of a single literal needs no gate of its own, the literal
+    // is attached directly to the root.
+    let term_ids: Vec<GateId> = terms
+        .iter()
+        .map(|(program, literals)| -> GateId {
+            if literals.len() == 1 {
+                return reducer.root();
+            }
+            reducer
+                .add_gate_with(GateBuilder::new(reducer.root()).program(*program))
+                .unwrap()
+        })
+        .collect();
+    // A literal is attached beneath the first term to use it and is
+    // shorted to every other; the inverting gates are added first so
+    // that the leaves are added, and so ordered, by input.
+    let mut literal_ids: BTreeMap<(usize, bool), GateId> = BTreeMap::new();
+    for inverted in [true, false].iter() {
+        for j in 0..inputs {
+            let users: Vec<GateId> = terms
+                .iter()
+                .zip(term_ids.iter())
+                .filter(|((_, ls), _)| -> bool { ls.contains(&(j, *inverted)) })
+                .map(|(_, id)| -> GateId { *id })
+                .collect();
+            let parent: GateId = match (users.first(), literal_ids.get(&(j, true))) {
+                (Some(user), _) => *user,
+                (None, Some(not)) => *not,
+                (None, None) => continue,
+            };
+            let id: GateId = reducer
+                .add_gate_with(
+                    GateBuilder::new(parent)
+                        .program(true)
+                        .configuration(*inverted),
+                )
+                .unwrap();
+            for user in users.iter().skip(1) {
+                reducer.short(*user, id).unwrap();
+            }
+            literal_ids.insert((j, *inverted), id);
+        }
+    }
+    for j in 0..inputs {
+        if let (Some(not), Some(leaf)) = (literal_ids.get(&(j, true)), literal_ids.get(&(j, false)))
+        {
+            if !reducer.children(*not).unwrap().contains(leaf) {
+                reducer.short(*not, *leaf).unwrap();
+            }
+        }
+    }
+    let _: bool = reducer.output();
+    reducer
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::reducer::api::{AddGate, Output, Reconfigure, Reinput, Reprogram, Short};
+    use crate::reducer::{BTreeReducer, GateBuilder, GateId};
+    use crate::truth_table::TruthTable;
+    use crate::Error;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    fn xor() -> Result<BTreeReducer<bool>, Error> {
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let series_0 = reducer.add_gate(reducer.root())?;
+        let parallel_1 = reducer.add_gate(series_0)?;
+        let series_1 = reducer.add_gate(series_0)?;
+        let input_0 = reducer.add_gate(parallel_1)?;
+        let input_1 = reducer.add_gate(parallel_1)?;
+        reducer.short(series_1, input_0)?;
+        reducer.short(series_1, input_1)?;
+        reducer.reprogram(String::from("010100"))?;
+        reducer.reconfigure(String::from("000100"))?;
+        Ok(reducer)
+    }
+
+    #[test]
+    fn xor_truth_table() -> Result<(), Error> {
+        let reducer: BTreeReducer<bool> = xor()?;
+        let table: TruthTable = reducer.truth_table()?;
+        assert_eq!(table.inputs(), 2);
+        assert_eq!(table.rows(), 4);
+        assert_eq!(table.words(), &[0b0110]);
+        assert_eq!(table.count_ones(), 2);
+        assert_eq!(table.get(1), Some(true));
+        assert_eq!(table.get(4), None);
+        assert_eq!(table.input(1), Vec::from([true, false]));
+        assert_eq!(table.to_string(), "00 0\n10 1\n01 1\n11 0\n");
+
+        // The reducer itself is not transitioned.
+        assert_eq!(reducer, xor()?);
+        Ok(())
+    }
+
+    #[test]
+    fn compare() -> Result<(), Error> {
+        let mut reducer: BTreeReducer<bool> = xor()?;
+        let xor: TruthTable = reducer.truth_table()?;
+        reducer.reconfigure(String::from("100100"))?;
+        let xnor: TruthTable = reducer.truth_table()?;
+        assert_ne!(xor, xnor);
+        assert_eq!(xnor.to_string(), "00 1\n10 0\n01 0\n11 1\n");
+        reducer.reconfigure(String::from("000100"))?;
+        assert_eq!(reducer.truth_table()?, xor);
+        Ok(())
+    }
+
+    #[test]
+    fn many_inputs() -> Result<(), Error> {
+        // An OR of eight (8) inputs spans several words.
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        let mut leaves: Vec<GateId> = Vec::new();
+        for _ in 0..8 {
+            leaves.push(reducer.add_gate(reducer.root())?);
+        }
+        let table: TruthTable = reducer.truth_table()?;
+        assert_eq!(table.rows(), 256);
+        assert_eq!(table.words().len(), 4);
+        assert_eq!(table.count_ones(), 255);
+        for row in [0, 1, 63, 64, 200, 255].iter() {
+            reducer.reinput(table.input(*row))?;
+            let output: bool = reducer.output();
+            assert_eq!(table.get(*row), Some(output));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn from_bits() -> Result<(), Error> {
+        let table: TruthTable = TruthTable::from_bits(2, &[false, true, true, false])?;
+        assert_eq!(table, xor()?.truth_table()?);
+        assert_eq!(
+            table,
+            TruthTable::from_fn(2, |iv: Vec<bool>| -> bool { iv[0] != iv[1] })?
+        );
+        assert!(TruthTable::from_bits(2, &[false, true, true]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn max_inputs() -> Result<(), Error> {
+        // Beyond the limit the number of rows would overflow long before
+        // the words could be allocated.
+        for inputs in [TruthTable::MAX_INPUTS + 1, 64, usize::MAX].iter() {
+            assert_eq!(
+                TruthTable::from_fn(*inputs, |_| -> bool { true }),
+                Err(Error::ImproperDimension)
+            );
+            assert_eq!(
+                TruthTable::from_bits(*inputs, &[]),
+                Err(Error::ImproperDimension)
+            );
+        }
+        let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+        for _ in 0..TruthTable::MAX_INPUTS + 1 {
+            reducer.add_gate_with(GateBuilder::new(reducer.root()))?;
+        }
+        assert_eq!(reducer.truth_table(), Err(Error::ImproperDimension));
+        assert_eq!(reducer.minimize(), Err(Error::ImproperDimension));
+        Ok(())
+    }
+
+    #[test]
+    fn from_truth_table() -> Result<(), Error> {
+        // Every function of one (1), two (2) and three (3) inputs.
+        for inputs in 1..4 {
+            let rows: usize = 1 << inputs;
+            for f in 0..(1usize << rows) {
+                let bits: Vec<bool> = (0..rows)
+                    .map(|row| -> bool { (f >> row) & 1 == 1 })
+                    .collect();
+                let table: TruthTable = TruthTable::from_bits(inputs, &bits)?;
+                let reducer: BTreeReducer<bool> = BTreeReducer::from_truth_table(&table)?;
+                assert_eq!(reducer.input_gates().len(), inputs);
+                assert_eq!(reducer.truth_table()?, table);
+            }
+        }
+
+        // Majority of five (5) and parity of six (6) inputs.
+        let majority: TruthTable = TruthTable::from_fn(5, |iv: Vec<bool>| -> bool {
+            iv.iter().filter(|i| -> bool { **i }).count() > 2
+        })?;
+        let parity: TruthTable = TruthTable::from_fn(6, |iv: Vec<bool>| -> bool {
+            iv.iter().fold(false, |p, i| -> bool { p != *i })
+        })?;
+        for table in [majority, parity].iter() {
+            let mut reducer: BTreeReducer<bool> = BTreeReducer::from_truth_table(table)?;
+            assert_eq!(&reducer.truth_table()?, table);
+            for row in 0..table.rows() {
+                reducer.reinput(table.input(row))?;
+                let output: bool = reducer.output();
+                assert_eq!(Some(output), table.get(row));
+            }
+        }
+
+        assert!(
+            BTreeReducer::from_truth_table(&TruthTable::from_fn(0, |_| -> bool { true })?).is_err()
+        );
+        Ok(())
+    }
+}
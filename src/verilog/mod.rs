@@ -0,0 +1,270 @@
+//! Structural Verilog export for `BTreeReducer<bool>`.
+//!
+//! ```text
+//! module xor_gate (
+//!     input wire a,
+//!     input wire b,
+//!     output wire and_
+//! );
+//!     wire or_;
+//!     wire nand_;
+//!     assign or_ = a | b;
+//!     assign nand_ = ~(a & b);
+//!     assign and_ = or_ & nand_;
+//! endmodule
+//! ```
+//!
+//! Every input gate is an input port, ordered as for `Reinput<Vec<bool>>`,
+//! and every output gate, beginning with the root, is an output port.
+//! Every other gate is a wire. Ports and wires are named as by
+//! `netlist::print`, followed by underscores where the name is a Verilog
+//! keyword or already taken. The module is purely combinational and
+//! contains only continuous assignments, so it is synthesizable as is.
+
+use crate::graph::{names, postorder};
+use crate::reducer::api::{Configuration, Program};
+use crate::reducer::{BTreeReducer, Gate, GateId};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+mod test;
+
+/// The reserved words of Verilog-2005, none of which may name a port
+/// or wire.
+const KEYWORDS: [&str; 124] = [
+    "always",
+    "and",
+    "assign",
+    "automatic",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cell",
+    "cmos",
+    "config",
+    "deassign",
+    "default",
+    "defparam",
+    "design",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endconfig",
+    "endfunction",
+    "endgenerate",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "genvar",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "incdir",
+    "include",
+    "initial",
+    "inout",
+    "input",
+    "instance",
+    "integer",
+    "join",
+    "large",
+    "liblist",
+    "library",
+    "localparam",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "noshowcancelled",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "pulsestyle_ondetect",
+    "pulsestyle_onevent",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "showcancelled",
+    "signed",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "unsigned",
+    "use",
+    "uwire",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+];
+
+/// Prints a `BTreeReducer<bool>` as a Verilog module named `module`,
+/// which must itself be a valid Verilog identifier.
+///
+/// A gate with several children is an AND (series) or OR (parallel) of
+/// their outputs, inverted into a NAND or NOR if it is configured. A
+/// gate with a single child, or an input gate, passes its input through
+/// XORed with its configuration. Only the gates on which some output
+/// depends are assigned.
+pub fn print(reducer: &BTreeReducer<bool>, module: &str) -> String {
+    let names: BTreeMap<GateId, String> = names(reducer);
+    let mut taken: BTreeSet<String> = BTreeSet::new();
+    let inputs: Vec<GateId> = reducer.input_gates();
+    let outputs: Vec<GateId> = reducer.outputs();
+    let mut ports: Vec<String> = Vec::new();
+    // The signal carrying the output of every gate.
+    let mut signals: BTreeMap<GateId, String> = BTreeMap::new();
+    let mut input_ports: BTreeMap<GateId, String> = BTreeMap::new();
+    for id in inputs.iter() {
+        let name: String = fresh(&mut taken, &names[id]);
+        ports.push(format!("    input wire {}", name));
+        input_ports.insert(*id, name);
+    }
+    let mut output_ports: Vec<(GateId, String)> = Vec::new();
+    for id in outputs.iter() {
+        let name: String = fresh(&mut taken, &names[id]);
+        ports.push(format!("    output wire {}", name));
+        output_ports.push((*id, name.clone()));
+        signals.entry(*id).or_insert(name);
+    }
+
+    let order: Vec<GateId> = postorder(reducer, &outputs);
+    let mut wires: String = String::new();
+    let mut assignments: String = String::new();
+    for id in order.iter() {
+        let gate: &Gate<bool> = reducer.gate(*id).unwrap();
+        let operands: Vec<String> = match input_ports.get(id) {
+            Some(port) => Vec::from([port.clone()]),
+            None => reducer
+                .children(*id)
+                .into_iter()
+                .flatten()
+                .map(|child| -> String { signals[child].clone() })
+                .collect(),
+        };
+        // An input gate which is not configured needs no assignment of
+        // its own unless it drives an output port.
+        if operands.len() == 1 && !gate.configuration() && !signals.contains_key(id) {
+            signals.insert(*id, operands[0].clone());
+            continue;
+        }
+        let signal: String = match signals.get(id) {
+            Some(signal) => signal.clone(),
+            None => {
+                let name: String = fresh(&mut taken, &names[id]);
+                wires.push_str(&format!("    wire {};\n", name));
+                signals.insert(*id, name.clone());
+                name
+            }
+        };
+        assignments.push_str(&format!(
+            "    assign {} = {};\n",
+            signal,
+            expression(&operands, gate.program(), gate.configuration())
+        ));
+    }
+    // An output gate may be repeated or drive another output port.
+    for (id, port) in output_ports.iter() {
+        if signals[id] != *port {
+            assignments.push_str(&format!("    assign {} = {};\n", port, signals[id]));
+        }
+    }
+
+    let mut s: String = format!("module {} (\n", module);
+    s.push_str(&ports.join(",\n"));
+    s.push_str("\n);\n");
+    s.push_str(&wires);
+    s.push_str(&assignments);
+    s.push_str("endmodule\n");
+    s
+}
+
+/// The expression of a gate with the outputs `operands` of its children.
+fn expression(operands: &[String], program: bool, configuration: bool) -> String {
+    if operands.len() == 1 {
+        return if configuration {
+            format!("{} ^ 1'b1", operands[0])
+        } else {
+            operands[0].clone()
+        };
+    }
+    let joined: String = operands.join(if program { " & " } else { " | " });
+    if configuration {
+        format!("~({})", joined)
+    } else {
+        joined
+    }
+}
+
+/// Claims `name`, followed by as many underscores as needed for it to
+/// be neither a keyword nor already taken.
+fn fresh(taken: &mut BTreeSet<String>, name: &str) -> String {
+    let mut name: String = String::from(name);
+    while KEYWORDS.contains(&name.as_str()) || taken.contains(&name) {
+        name.push('_');
+    }
+    taken.insert(name.clone());
+    name
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod unit_tests {
+    use crate::expression;
+    use crate::netlist;
+    use crate::netlist::test::XOR;
+    use crate::parse::ParseError;
+    use crate::reducer::api::{Output, Reinput};
+    use crate::reducer::BTreeReducer;
+    use crate::verilog::print;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// Evaluates the output ports of a module printed by `print`, which
+    /// only ever assigns the expressions `print` emits.
+    fn simulate(verilog: &str, iv: &[bool]) -> Vec<bool> {
+        let mut inputs: BTreeMap<&str, bool> = BTreeMap::new();
+        let mut outputs: Vec<&str> = Vec::new();
+        let mut assignments: BTreeMap<&str, &str> = BTreeMap::new();
+        for line in verilog.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("input wire ") {
+                inputs.insert(name.trim_end_matches(','), iv[inputs.len()]);
+            } else if let Some(name) = line.strip_prefix("output wire ") {
+                outputs.push(name.trim_end_matches(','));
+            } else if let Some(assignment) = line.strip_prefix("assign ") {
+                let (name, expression) =
+                    assignment.trim_end_matches(';').split_once(" = ").unwrap();
+                assignments.insert(name, expression);
+            }
+        }
+        fn evaluate(
+            e: &str,
+            inputs: &BTreeMap<&str, bool>,
+            assignments: &BTreeMap<&str, &str>,
+        ) -> bool {
+            if let Some(inner) = e.strip_prefix("~(").and_then(|e| e.strip_suffix(')')) {
+                !evaluate(inner, inputs, assignments)
+            } else if let Some(operand) = e.strip_suffix(" ^ 1'b1") {
+                !evaluate(operand, inputs, assignments)
+            } else if e.contains(" & ") {
+                e.split(" & ")
+                    .all(|o| -> bool { evaluate(o, inputs, assignments) })
+            } else if e.contains(" | ") {
+                e.split(" | ")
+                    .any(|o| -> bool { evaluate(o, inputs, assignments) })
+            } else if let Some(value) = inputs.get(e) {
+                *value
+            } else {
+                evaluate(assignments[e], inputs, assignments)
+            }
+        }
+        outputs
+            .iter()
+            .map(|o| -> bool { evaluate(o, &inputs, &assignments) })
+            .collect()
+    }
+
+    #[test]
+    fn print_xor() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = netlist::parse(XOR)?;
+        assert_eq!(
+            print(&reducer, "xor_gate"),
+            concat!(
+                "module xor_gate (\n",
+                "    input wire a,\n",
+                "    input wire b,\n",
+                "    output wire and_\n",
+                ");\n",
+                "    wire or_;\n",
+                "    wire nand_;\n",
+                "    assign or_ = a | b;\n",
+                "    assign nand_ = ~(a & b);\n",
+                "    assign and_ = or_ & nand_;\n",
+                "endmodule\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn print_inverted_inputs() -> Result<(), ParseError> {
+        let reducer: BTreeReducer<bool> = netlist::parse(
+            "
+            root y configuration=1
+            gate x y configuration=1
+            gate wire x
+            ",
+        )?;
+        assert_eq!(
+            print(&reducer, "buffer"),
+            concat!(
+                "module buffer (\n",
+                "    input wire wire_,\n",
+                "    output wire y\n",
+                ");\n",
+                "    wire x;\n",
+                "    assign x = wire_ ^ 1'b1;\n",
+                "    assign y = x ^ 1'b1;\n",
+                "endmodule\n",
+            )
+        );
+        // A lone root is both an input and an output port.
+        let reducer: BTreeReducer<bool> = BTreeReducer::new();
+        assert_eq!(
+            print(&reducer, "lone"),
+            concat!(
+                "module lone (\n",
+                "    input wire g0,\n",
+                "    output wire g0_\n",
+                ");\n",
+                "    assign g0_ = g0;\n",
+                "endmodule\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn simulate_outputs() -> Result<(), ParseError> {
+        // Every output gate is a port of its own.
+        let adder: BTreeReducer<bool> = netlist::parse(
+            "
+            root sum program=1
+            gate or sum
+            gate nand sum program=1 configuration=1
+            gate a or
+            gate b or
+            short nand a
+            short nand b
+            output nand
+            ",
+        )?;
+        let verilog: String = print(&adder, "half_adder");
+        for iv in [[false, false], [true, false], [false, true], [true, true]].iter() {
+            let mut reducer: BTreeReducer<bool> = adder.clone();
+            reducer.reinput(Vec::from(*iv)).unwrap();
+            let outputs: Vec<bool> = reducer.output();
+            assert_eq!(outputs, Vec::from([iv[0] != iv[1], !(iv[0] && iv[1])]));
+            assert_eq!(simulate(&verilog, iv), outputs);
+        }
+
+        for s in [
+            "a",
+            "!a",
+            "a ^ b ^ c",
+            "(a & b) | !c",
+            "!(a | b) & (c | !d)",
+            "let x = a & b; x | !x & c",
+        ]
+        .iter()
+        {
+            let reducer: BTreeReducer<bool> = expression::parse(s)?;
+            let verilog: String = print(&reducer, "f");
+            for row in 0..1 << reducer.input_gates().len() {
+                let iv: Vec<bool> = reducer.truth_table().unwrap().input(row);
+                assert_eq!(
+                    simulate(&verilog, &iv),
+                    Vec::from([reducer.truth_table().unwrap().get(row).unwrap()]),
+                    "{}",
+                    s
+                );
+            }
+        }
+        Ok(())
+    }
+}
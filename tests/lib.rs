@@ -1 +1,206 @@
+use btree_reducer::prelude::*;
+use btree_reducer::{bool_to_str, try_str_to_bool};
+use std::collections::BTreeSet;
 
+#[test]
+fn str_to_bool() -> Result<(), Error> {
+    let v: Vec<bool> = try_str_to_bool(String::from("0110"))?;
+    assert_eq!(v, vec![false, true, true, false]);
+    assert_eq!(bool_to_str(v), String::from("0110"));
+    assert!(try_str_to_bool(String::from("01a0")).is_err());
+    Ok(())
+}
+
+#[test]
+fn xor_truth_table_trans_xnor_truth_table() -> Result<(), Error> {
+    let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+    let series_0: GateId = reducer.add_gate(reducer.root())?;
+    let parallel_1: GateId = reducer.add_gate(series_0)?;
+    let series_1: GateId = reducer.add_gate(series_0)?;
+    let input_0: GateId = reducer.add_gate(parallel_1)?;
+    let input_1: GateId = reducer.add_gate(parallel_1)?;
+    reducer.short(series_1, input_0)?;
+    reducer.short(series_1, input_1)?;
+
+    reducer.reprogram(String::from("010100"))?;
+    reducer.reconfigure(String::from("000100"))?;
+
+    for (is, os) in [("00", "0"), ("10", "1"), ("01", "1"), ("11", "0")].iter() {
+        reducer.reinput(String::from(*is))?;
+
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), *is);
+
+        let configuration: String = reducer.configuration();
+        assert_eq!(configuration.as_str(), "000100");
+
+        let output: String = reducer.output();
+        assert_eq!(output.as_str(), *os);
+    }
+
+    // XOR -> XNOR
+    reducer.reconfigure(String::from("100100"))?;
+
+    for (is, os) in [("00", "1"), ("10", "0"), ("01", "0"), ("11", "1")].iter() {
+        reducer.reinput(String::from(*is))?;
+
+        let input: String = reducer.input();
+        assert_eq!(input.as_str(), *is);
+
+        let configuration: String = reducer.configuration();
+        assert_eq!(configuration.as_str(), "100100");
+
+        let output: String = reducer.output();
+        assert_eq!(output.as_str(), *os);
+    }
+    Ok(())
+}
+
+#[derive(PartialEq, PartialOrd, Ord, Eq, Clone, Copy, Debug, Default)]
+struct Letter(char);
+
+impl Transition<Letter> for Gate<Letter> {
+    fn transition(&self) -> Letter {
+        Letter('y')
+    }
+}
+
+impl Output<Letter> for Gate<Letter> {
+    type Error = Error;
+    fn output(&mut self) -> Letter {
+        let vowels: BTreeSet<char> = "aeiouy".chars().collect();
+        if vowels.contains(&self.input().0) {
+            Letter('y')
+        } else {
+            Letter('n')
+        }
+    }
+}
+
+#[test]
+fn vowels() -> Result<(), Error> {
+    let mut reducer: BTreeReducer<Letter> = BTreeReducer::new();
+    reducer.add_gate(reducer.root())?;
+    reducer.add_gate(reducer.root())?;
+    reducer.add_gate(reducer.root())?;
+
+    reducer.reprogram(vec![Letter('n'), Letter('\0'), Letter('\0'), Letter('\0')])?;
+
+    for (word, contains_vowel) in [
+        ("fox", 'y'),
+        ("cat", 'y'),
+        ("psm", 'n'),
+        ("ibm", 'y'),
+        ("dog", 'y'),
+        ("tls", 'n'),
+    ]
+    .iter()
+    {
+        let input: Vec<Letter> = word.chars().map(Letter).collect();
+        reducer.reinput(input)?;
+        let output: Letter = reducer.output();
+        assert_eq!(output, Letter(*contains_vowel));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn xor() -> Result<BTreeReducer<bool>, Error> {
+    let mut reducer: BTreeReducer<bool> = BTreeReducer::new();
+    let series_0: GateId =
+        reducer.add_gate_with(GateBuilder::new(reducer.root()).program(true).label("and"))?;
+    let parallel_1: GateId = reducer.add_gate_with(GateBuilder::new(series_0).label("or"))?;
+    let series_1: GateId = reducer.add_gate_with(
+        GateBuilder::new(series_0)
+            .program(true)
+            .configuration(true)
+            .label("nand"),
+    )?;
+    let input_0: GateId = reducer.add_gate_with(GateBuilder::new(parallel_1).label("a"))?;
+    let input_1: GateId = reducer.add_gate_with(GateBuilder::new(parallel_1).label("b"))?;
+    reducer.short(series_1, input_0)?;
+    reducer.short(series_1, input_1)?;
+    // The OR gate is promoted so that `outputs` is encoded as well.
+    reducer.add_output(parallel_1)?;
+    reducer.reinput(String::from("10"))?;
+    Ok(reducer)
+}
+
+#[cfg(feature = "serde")]
+fn assert_round_trip(mut reducer: BTreeReducer<bool>, mut decoded: BTreeReducer<bool>) {
+    assert_eq!(decoded, reducer);
+    assert_eq!(decoded.outputs(), reducer.outputs());
+    assert_eq!(
+        decoded.gate(GateId::default()),
+        reducer.gate(GateId::default())
+    );
+    for is in ["00", "10", "01", "11"].iter() {
+        reducer.reinput(String::from(*is)).unwrap();
+        decoded.reinput(String::from(*is)).unwrap();
+        let output: Vec<bool> = reducer.output();
+        let decoded_output: Vec<bool> = decoded.output();
+        assert_eq!(decoded_output, output);
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json() -> Result<(), Error> {
+    let reducer: BTreeReducer<bool> = xor()?;
+    let encoded: String = serde_json::to_string(&reducer).unwrap();
+    let decoded: BTreeReducer<bool> = serde_json::from_str(&encoded).unwrap();
+    assert_round_trip(reducer, decoded);
+
+    // The root gate is required.
+    let encoded: &str = r#"{"gates":[],"edges":[],"outputs":[0],"next":1}"#;
+    assert!(serde_json::from_str::<BTreeReducer<bool>>(encoded).is_err());
+
+    // Shorts may not introduce a cycle.
+    let encoded: &str = concat!(
+        r#"{"gates":["#,
+        r#"{"id":0,"input":false,"configuration":false,"program":false,"label":null},"#,
+        r#"{"id":1,"input":false,"configuration":false,"program":false,"label":null}"#,
+        r#"],"edges":[[0,1],[1,0]],"outputs":[0],"next":2}"#
+    );
+    assert!(serde_json::from_str::<BTreeReducer<bool>>(encoded).is_err());
+
+    // The outputs must begin with the root.
+    let encoded: &str = concat!(
+        r#"{"gates":["#,
+        r#"{"id":0,"input":false,"configuration":false,"program":false,"label":null},"#,
+        r#"{"id":1,"input":false,"configuration":false,"program":false,"label":null}"#,
+        r#"],"edges":[[0,1]],"outputs":[1,0],"next":2}"#
+    );
+    assert!(serde_json::from_str::<BTreeReducer<bool>>(encoded).is_err());
+
+    // Without any outputs the root is the only output.
+    let encoded: &str = concat!(
+        r#"{"gates":["#,
+        r#"{"id":0,"input":false,"configuration":false,"program":false,"label":null},"#,
+        r#"{"id":1,"input":false,"configuration":false,"program":false,"label":null}"#,
+        r#"],"edges":[[0,1]],"next":2}"#
+    );
+    let decoded: BTreeReducer<bool> = serde_json::from_str(encoded).unwrap();
+    assert_eq!(decoded.outputs(), Vec::from([decoded.root()]));
+    Ok(())
+}
+
+#[cfg(feature = "serde_cbor")]
+#[test]
+fn serde_cbor() -> Result<(), Error> {
+    let reducer: BTreeReducer<bool> = xor()?;
+    let encoded: Vec<u8> = serde_cbor::to_vec(&reducer).unwrap();
+    let decoded: BTreeReducer<bool> = serde_cbor::from_slice(&encoded).unwrap();
+    assert_round_trip(reducer, decoded);
+    Ok(())
+}
+
+#[cfg(feature = "serde_yaml")]
+#[test]
+fn serde_yaml() -> Result<(), Error> {
+    let reducer: BTreeReducer<bool> = xor()?;
+    let encoded: String = serde_yaml::to_string(&reducer).unwrap();
+    let decoded: BTreeReducer<bool> = serde_yaml::from_str(&encoded).unwrap();
+    assert_round_trip(reducer, decoded);
+    Ok(())
+}